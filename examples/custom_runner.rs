@@ -0,0 +1,59 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy_rrise::emitter_listener::RrEmitterBundle;
+use bevy_rrise::plugin::{RrEngine, RriseBasicSettings, RrisePlugin};
+use bevy_rrise::sound_engine::load_bank;
+use std::time::{Duration, Instant};
+
+// How long the app runs before shutting itself down
+const RUN_DURATION: Duration = Duration::from_secs(3);
+
+const FRAME_DURATION: Duration = Duration::from_millis(16);
+
+/// Headless app with its own runner, as an editor embedding the game would have: the app never
+/// sends an AppExit event, so the runner terminates the sound engine itself once it stops updating.
+fn main() {
+    App::new()
+        .add_plugins(MinimalPlugins)
+        .add_plugin(LogPlugin {
+            filter: "bevy_rrise=debug".to_string(),
+            ..default()
+        })
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(
+            RrisePlugin::default().with_plugin_settings(RriseBasicSettings {
+                terminate_on_app_exit: false,
+                ..default()
+            }),
+        )
+        .add_startup_system(setup_scene)
+        .set_runner(run_for_a_while)
+        .run();
+}
+
+fn setup_scene(mut commands: Commands) {
+    if let Err(akr) = load_bank("TheBank.bnk") {
+        panic!("Couldn't load TheBank: {}", akr);
+    }
+
+    commands.spawn(RrEmitterBundle::new(Vec3::X).with_event("PlayOneShot", true));
+}
+
+fn run_for_a_while(mut app: App) {
+    let start = Instant::now();
+    while start.elapsed() < RUN_DURATION {
+        app.update();
+        std::thread::sleep(FRAME_DURATION);
+    }
+
+    if let Err(akr) = app.world.resource_mut::<RrEngine>().terminate() {
+        error!("Couldn't terminate the sound engine: {}", akr);
+    }
+}
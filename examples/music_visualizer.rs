@@ -5,17 +5,13 @@
 use bevy::log::LogPlugin;
 use bevy::prelude::*;
 use bevy_rrise::plugin::{CallbackChannel, RriseLabel, RrisePlugin};
+use bevy_rrise::sound_engine::load_bank;
 use bevy_rrise::sound_engine::PostEventAtLocation;
-use bevy_rrise::AkCallbackEvent;
+use bevy_rrise::{AkCallbackEvent, CallbackPreset};
 use rrise::query_params::{get_rtpc_value, RtpcValueType};
 use rrise::settings;
-use rrise::sound_engine::load_bank_by_name;
 use rrise::{AkAuxBusID, AkCallbackInfo, AkCallbackType, AkResult, AkRtpcValue};
 use rrise_headers::rr;
-use std::path::PathBuf;
-
-#[cfg(windows)]
-use cc;
 
 fn main() {
     App::new()
@@ -24,14 +20,11 @@ fn main() {
             ..default()
         }))
         .add_plugin(
-            RrisePlugin::default().with_engine_settings(
-                settings::AkInitSettings {
-                    #[cfg(not(wwrelease))]
-                    install_assert_hook: true,
-                    ..default()
-                }
-                .with_plugin_dll_path(get_example_dll_path()),
-            ),
+            RrisePlugin::default().with_engine_settings(settings::AkInitSettings {
+                #[cfg(not(wwrelease))]
+                install_assert_hook: true,
+                ..default()
+            }),
         )
         .insert_resource(Meters {
             meters: [
@@ -123,13 +116,13 @@ fn update_beat_bar_times(
 // Start music by spawning a static sound emitter
 #[tracing::instrument(level = "debug", skip_all)]
 fn start_music(cb_channel: Res<CallbackChannel>) -> Result<(), AkResult> {
-    if let Err(akr) = load_bank_by_name(rr::bnk::TheBank) {
+    if let Err(akr) = load_bank(rr::bnk::TheBank) {
         error!("Couldn't load TheBank: {}", akr);
         return Err(akr);
     }
 
     PostEventAtLocation::new(rr::ev::PlayMeteredMusic, Transform::default())
-        .flags(AkCallbackType::AK_MusicSyncBeat | AkCallbackType::AK_MusicSyncBar)
+        .flags(CallbackPreset::BeatsAndBars)
         .post(Some(cb_channel.clone()))?;
 
     Ok(())
@@ -253,59 +246,3 @@ fn spawn_meter(
         BandMeter(index),
     ));
 }
-
-fn get_example_dll_path() -> String {
-    let wwise_sdk = PathBuf::from(std::env::var("WWISESDK").expect("env var WWISESDK not found"));
-
-    let mut path;
-    #[cfg(windows)]
-    {
-        let vs_version = cc::windows_registry::find_vs_version().expect("No MSVC install found");
-
-        let wwise_vc = match vs_version {
-            cc::windows_registry::VsVers::Vs14 => "x64_vc140",
-            cc::windows_registry::VsVers::Vs15 => "x64_vc150",
-            cc::windows_registry::VsVers::Vs16 => "x64_vc160",
-            cc::windows_registry::VsVers::Vs17 => "x64_vc170",
-            _ => panic!("Unsupported MSVC version: {:?}", vs_version),
-        };
-        path = wwise_sdk.join(wwise_vc);
-
-        if !path.exists() {
-            panic!(
-                "Could not find {}.\n\
-                You are using MSVC {:?} but the {} Wwise SDK target probably wasn't installed or \
-                doesn't exist for this version of Wwise.\n\
-                Note that Vs17 (Visual Studio 2022) is supported since Wwise 2021.1.10 only.",
-                path.to_str().unwrap(),
-                vs_version,
-                wwise_vc
-            )
-        }
-    }
-    #[cfg(target_os = "linux")]
-    {
-        path = wwise_sdk.join("Linux_x64");
-    }
-
-    path = if cfg!(wwdebug) {
-        path.join("Debug")
-    } else if cfg!(wwrelease) {
-        path.join("Release")
-    } else {
-        path.join("Profile")
-    };
-
-    // -- KNOWN ISSUE ON WINDOWS --
-    // If WWISESDK contains spaces, the DLLs can't be discovered.
-    // Help wanted!
-    // Anyway, if you truly wanted to deploy something based on this crate with dynamic loading of
-    // Wwise plugins, you would need to make sure to deploy any Wwise shared library (SO or DLL)
-    // along your executable. You can't expect your players to have Wwise installed!
-    // You can also just statically link everything, using this crate features. Enabling a feature
-    // then forcing a rebuild will statically link the selected plugins instead of letting Wwise
-    // look for their shared libraries at runtime.
-    // Legal: Remember that Wwise is a licensed product, and you can't distribute their code,
-    // statically linked or not, without a proper license.
-    path.join("bin").into_os_string().into_string().unwrap()
-}
@@ -0,0 +1,116 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+use bevy_rrise::emitter_listener::{RrEmitter2dBundle, RrListenerBundle};
+use bevy_rrise::plugin::{RriseBasicSettings, RrisePlugin};
+use bevy_rrise::sound_engine::load_bank;
+use rrise::settings;
+
+// Converts screen space distances to distances the attenuation curves of the Wwise project expect
+const PIXELS_PER_METER: f32 = 20_f32;
+
+// Away from the origin, so that listener relative emitters must be offset by the listener position
+const LISTENER_POSITION: Vec2 = Vec2::new(640., 360.);
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(LogPlugin {
+            filter: "bevy_rrise=debug,wgpu=error".to_string(),
+            ..default()
+        }))
+        .add_plugin(
+            RrisePlugin::default()
+                .with_engine_settings(settings::AkInitSettings {
+                    #[cfg(not(wwrelease))]
+                    install_assert_hook: true,
+                    ..default()
+                })
+                .with_plugin_settings(RriseBasicSettings {
+                    spawn_default_listener: false,
+                    ..default()
+                }),
+        )
+        .add_startup_system(setup_scene)
+        .add_system(play_on_click)
+        .add_system(fade_out_markers)
+        .run();
+}
+
+#[derive(Component)]
+struct Marker(Timer);
+
+/// Setup 2D camera and a sprite figuring the listener in the middle of the screen
+fn setup_scene(mut commands: Commands) {
+    commands.spawn(Camera2dBundle {
+        transform: Transform::from_translation(LISTENER_POSITION.extend(999.9)),
+        ..default()
+    });
+
+    commands.spawn(SpriteBundle {
+        sprite: Sprite {
+            color: Color::WHITE,
+            custom_size: Some(Vec2::splat(24.)),
+            ..default()
+        },
+        transform: Transform::from_translation(LISTENER_POSITION.extend(0.)),
+        ..default()
+    });
+    commands.spawn(RrListenerBundle::new(
+        (LISTENER_POSITION / PIXELS_PER_METER).extend(0.),
+    ));
+
+    if let Err(akr) = load_bank("TheBank.bnk") {
+        panic!("Couldn't load TheBank: {}", akr);
+    }
+}
+
+/// Plays a one shot sound where the user clicked; left clicks are heard on the left, right clicks
+/// on the right
+fn play_on_click(mut commands: Commands, windows: Res<Windows>, buttons: Res<Input<MouseButton>>) {
+    if !buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(window) = windows.get_primary() else {
+        return;
+    };
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+
+    // Relative to the center of the screen, where the listener is
+    let position = cursor - Vec2::new(window.width(), window.height()) / 2.;
+
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::RED,
+                custom_size: Some(Vec2::splat(16.)),
+                ..default()
+            },
+            transform: Transform::from_translation((LISTENER_POSITION + position).extend(0.)),
+            ..default()
+        },
+        Marker(Timer::from_seconds(1., TimerMode::Once)),
+    ));
+
+    commands
+        .spawn(RrEmitter2dBundle::new(position / PIXELS_PER_METER).with_event("PlayOneShot", true));
+}
+
+fn fade_out_markers(
+    mut commands: Commands,
+    mut markers: Query<(Entity, &mut Sprite, &mut Marker)>,
+    time: Res<Time>,
+) {
+    for (e, mut sprite, mut marker) in markers.iter_mut() {
+        marker.0.tick(time.delta());
+        sprite.color.set_a(marker.0.percent_left());
+        if marker.0.finished() {
+            commands.entity(e).despawn();
+        }
+    }
+}
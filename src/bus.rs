@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::soundbanks_info::RrSoundbanksInfo;
+use bevy::prelude::*;
+use rrise::AkUniqueID;
+use serde_json::Value;
+use std::path::Path;
+
+/// Reads the `SoundbanksInfo.json` file generated along the soundbanks.
+pub(crate) fn read_soundbanks_metadata(path: &Path) -> Option<Value> {
+    let metadata = match std::fs::read_to_string(path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            debug!(
+                "No soundbanks metadata available: couldn't read {} - {}",
+                path.to_string_lossy(),
+                e
+            );
+            return None;
+        }
+    };
+
+    match serde_json::from_str(&metadata) {
+        Ok(metadata) => Some(metadata),
+        Err(e) => {
+            warn!("Couldn't parse {} - {}", path.to_string_lossy(), e);
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A bus of the Wwise project, as described in the soundbanks metadata.
+pub struct RrBusInfo {
+    pub id: AkUniqueID,
+    pub name: String,
+
+    /// Path of the bus in the Wwise project, like
+    /// `\Master-Mixer Hierarchy\Default Work Unit\Master Audio Bus\Music`.
+    pub path: String,
+
+    /// ID of the parent bus; `None` for top level buses.
+    pub parent: Option<AkUniqueID>,
+
+    /// Whether this is an auxiliary bus.
+    pub aux: bool,
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+/// Bus hierarchy of the Wwise project, for generic tools like mixer UIs or settings menus.
+///
+/// Derived from the [RrSoundbanksInfo], parsed from the `SoundbanksInfo.json` file generated
+/// along the soundbanks; enable *Generate JSON Metadata* in the SoundBanks settings of your Wwise
+/// project. Empty if this file can't be found.
+pub struct RrBusHierarchy {
+    buses: Vec<RrBusInfo>,
+}
+
+impl RrBusHierarchy {
+    pub(crate) fn from_info(info: &RrSoundbanksInfo) -> Self {
+        let mut buses = info.buses().to_vec();
+
+        for i in 0..buses.len() {
+            let parent_path = buses[i].path.rsplit_once('\\').map(|(parent, _)| parent);
+            buses[i].parent = parent_path.and_then(|parent_path| {
+                buses
+                    .iter()
+                    .find(|bus| bus.path == parent_path)
+                    .map(|bus| bus.id)
+            });
+        }
+
+        debug!("Found {} buses in the soundbanks metadata", buses.len());
+        Self { buses }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &RrBusInfo> {
+        self.buses.iter()
+    }
+
+    pub fn get(&self, id: AkUniqueID) -> Option<&RrBusInfo> {
+        self.buses.iter().find(|bus| bus.id == id)
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&RrBusInfo> {
+        self.buses.iter().find(|bus| bus.name == name)
+    }
+
+    /// Top level buses, usually just the master audio bus.
+    pub fn roots(&self) -> impl Iterator<Item = &RrBusInfo> {
+        self.buses.iter().filter(|bus| bus.parent.is_none())
+    }
+
+    pub fn children(&self, id: AkUniqueID) -> impl Iterator<Item = &RrBusInfo> {
+        self.buses.iter().filter(move |bus| bus.parent == Some(id))
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+//! Backends routing Wwise callbacks to the Bevy side of a [CallbackChannel].
+//! Callbacks of a single event can also be set apart in [RrEventCallbacks].
+//!
+//! To use another backend than the default one, insert your own [CallbackChannel] before adding
+//! the plugin:
+//! ```rust,ignore
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .insert_resource(CallbackChannel::with_queue(RrStdCallbackQueue::default()))
+//!     .add_plugin(RrisePlugin::default())
+//!     .run();
+//! ```
+
+#[cfg(doc)]
+use crate::plugin::{CallbackChannel, RriseLabel};
+use rrise::{AkCallbackInfo, AkUniqueID};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// Queue holding callbacks between the Wwise thread they are received on and
+/// [RriseLabel::RriseCallbackEventsPopulated], where they are drained in order.
+///
+/// The provided backends are unbounded on purpose: pushing can't block the Wwise thread, and
+/// dropping callbacks would lose `EndOfEvent`s, leaking the playing IDs and closures waiting for
+/// them. They are drained every frame, and debug builds warn if they keep growing.
+pub trait RrCallbackQueue: Send + Sync + 'static {
+    /// Called from the Wwise callback thread: must not block. Gives `cb_info` back if it can't be
+    /// queued.
+    fn push(&self, cb_info: AkCallbackInfo) -> Result<(), AkCallbackInfo>;
+
+    /// Called from a single Bevy system; callbacks must come out in the order they were pushed.
+    fn pop(&self) -> Option<AkCallbackInfo>;
+
+    /// Callbacks pushed but not popped yet.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+/// Unbounded crossbeam channel; the default backend.
+pub struct RrCrossbeamCallbackQueue {
+    sender: crossbeam_channel::Sender<AkCallbackInfo>,
+    receiver: crossbeam_channel::Receiver<AkCallbackInfo>,
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl Default for RrCrossbeamCallbackQueue {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self { sender, receiver }
+    }
+}
+
+#[cfg(feature = "crossbeam-channel")]
+impl RrCallbackQueue for RrCrossbeamCallbackQueue {
+    fn push(&self, cb_info: AkCallbackInfo) -> Result<(), AkCallbackInfo> {
+        self.sender
+            .try_send(cb_info)
+            .map_err(|err| err.into_inner())
+    }
+
+    fn pop(&self) -> Option<AkCallbackInfo> {
+        self.receiver.try_recv().ok()
+    }
+
+    fn len(&self) -> usize {
+        self.receiver.len()
+    }
+}
+
+/// Unbounded standard library channel; the default backend when the `crossbeam-channel` feature
+/// is disabled.
+///
+/// Both ends of the channel are behind a lock, as they aren't shareable. Callbacks are pushed from
+/// the Wwise callback thread only and popped from the draining system only, so these locks aren't
+/// contended.
+pub struct RrStdCallbackQueue {
+    sender: Mutex<mpsc::Sender<AkCallbackInfo>>,
+    receiver: Mutex<mpsc::Receiver<AkCallbackInfo>>,
+    len: AtomicUsize,
+}
+
+impl Default for RrStdCallbackQueue {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            sender: Mutex::new(sender),
+            receiver: Mutex::new(receiver),
+            len: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl RrCallbackQueue for RrStdCallbackQueue {
+    fn push(&self, cb_info: AkCallbackInfo) -> Result<(), AkCallbackInfo> {
+        self.len.fetch_add(1, Ordering::AcqRel);
+        if let Err(err) = self.sender.lock().unwrap().send(cb_info) {
+            self.len.fetch_sub(1, Ordering::AcqRel);
+            return Err(err.0);
+        }
+        Ok(())
+    }
+
+    fn pop(&self) -> Option<AkCallbackInfo> {
+        let cb_info = self.receiver.lock().unwrap().try_recv().ok()?;
+        self.len.fetch_sub(1, Ordering::AcqRel);
+        Some(cb_info)
+    }
+
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+}
+
+/// Callbacks of a single event, set apart by its [CallbackChannel] so that their consumer doesn't
+/// have to filter all the [AkCallbackEvent](crate::AkCallbackEvent)s.
+///
+/// Created by [CallbackChannel::subscribe()]; callbacks stop being set apart once it is dropped.
+/// Callbacks are filled in [RriseLabel::RriseCallbackEventsPopulated], like the
+/// [AkCallbackEvent](crate::AkCallbackEvent)s which still get them too.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn follow_music(
+///     cb_channel: Res<CallbackChannel>,
+///     mut music_callbacks: Local<Option<RrEventCallbacks>>,
+/// ) {
+///     let music_callbacks =
+///         music_callbacks.get_or_insert_with(|| cb_channel.subscribe("PlayMeteredMusic"));
+///     for cb_info in music_callbacks.drain() {
+///         // Only the callbacks of PlayMeteredMusic
+///     }
+/// }
+/// ```
+pub struct RrEventCallbacks {
+    pub(crate) event_id: AkUniqueID,
+    pub(crate) callbacks: Arc<Mutex<Vec<AkCallbackInfo>>>,
+}
+
+impl RrEventCallbacks {
+    pub fn event_id(&self) -> AkUniqueID {
+        self.event_id
+    }
+
+    /// Takes the callbacks received since the last call, in order.
+    pub fn drain(&self) -> Vec<AkCallbackInfo> {
+        std::mem::take(&mut *self.callbacks.lock().unwrap())
+    }
+}
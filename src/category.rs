@@ -0,0 +1,158 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::{RrEmitter, RrObjDesc, RrRegistered};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+#[derive(Debug, Clone, Component)]
+/// Category of the one-shot emitter on the same entity, like `"Impacts"` or `"Footsteps"`.
+///
+/// When the [RrCategoryBudgets] of a category is reached, a newly spawned one-shot either steals
+/// the slot of a lower priority one (which gets stopped), or doesn't play at all.
+///
+/// Only emitters auto-posting their event are concerned (see
+/// [RrEmitterBundle::with_event()](crate::emitter_listener::RrEmitterBundle::with_event())).
+pub struct RrCategory {
+    pub name: &'static str,
+
+    /// Higher priority one-shots are kept over lower priority ones; see also [RrPriority].
+    ///
+    /// Defaults to `0`.
+    pub priority: u8,
+}
+
+impl RrCategory {
+    pub fn new(name: &'static str) -> Self {
+        Self { name, priority: 0 }
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Component)]
+/// Priority offset of the emitter on the same entity, added to the priority of its [RrCategory]
+/// when [RrCategoryBudgets] are enforced.
+///
+/// Use it to make critical gameplay sounds win over decorative ones sharing their category,
+/// without a category per importance level.
+///
+/// *Remark* This priority stays on the bevy-rrise side: Wwise voice limits only use the priority
+/// authored in the Wwise project, as Rrise doesn't bind a way to offset it at runtime.
+pub struct RrPriority(pub i8);
+
+#[derive(Debug, Default, Clone, Resource)]
+/// Maximum number of simultaneous one-shots per [RrCategory].
+///
+/// Categories without a budget are not limited.
+///
+/// ### Example
+/// ```rust,ignore
+/// app.insert_resource(RrCategoryBudgets::default().with_budget("Impacts", 8));
+/// ```
+pub struct RrCategoryBudgets(HashMap<&'static str, usize>);
+
+impl RrCategoryBudgets {
+    pub fn with_budget(mut self, category: &'static str, max_one_shots: usize) -> Self {
+        self.set_budget(category, max_one_shots);
+        self
+    }
+
+    pub fn set_budget(&mut self, category: &'static str, max_one_shots: usize) {
+        self.0.insert(category, max_one_shots);
+    }
+
+    pub fn remove_budget(&mut self, category: &'static str) {
+        self.0.remove(category);
+    }
+
+    pub fn budget(&self, category: &str) -> Option<usize> {
+        self.0.get(category).copied()
+    }
+}
+
+// Runs before the registration of new emitters, so that rejected one-shots never get posted
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn enforce_category_budgets(
+    budgets: Res<RrCategoryBudgets>,
+    mut new_one_shots: Query<
+        (Entity, &mut RrEmitter, &RrCategory, Option<&RrPriority>),
+        (Added<RrEmitter>, Without<RrRegistered>),
+    >,
+    live_one_shots: Query<
+        (Entity, &RrEmitter, &RrCategory, Option<&RrPriority>),
+        With<RrRegistered>,
+    >,
+) {
+    if budgets.0.is_empty() {
+        return;
+    }
+
+    let mut candidates: Vec<_> = new_one_shots
+        .iter_mut()
+        .filter(|(_, rr, category, _)| rr.auto_post && budgets.0.contains_key(category.name))
+        .map(|(e, rr, category, offset)| (e, rr, category, priority(category, offset)))
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+
+    // Highest priorities get the free slots first; this way, new one-shots never get evicted by
+    // the following ones
+    candidates.sort_by_key(|&(_, _, _, priority)| std::cmp::Reverse(priority));
+
+    let mut live: HashMap<&str, Vec<(Entity, i16)>> = default();
+    for (e, rr, category, offset) in live_one_shots.iter() {
+        if rr.is_playing() {
+            live.entry(category.name)
+                .or_default()
+                .push((e, priority(category, offset)));
+        }
+    }
+
+    for (e, mut rr, category, new_priority) in candidates {
+        let budget = budgets.0[category.name];
+        let live = live.entry(category.name).or_default();
+        if live.len() < budget {
+            live.push((e, new_priority));
+            continue;
+        }
+
+        let weakest = live
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, (_, priority))| *priority)
+            .map(|(i, &(victim, priority))| (i, victim, priority));
+
+        match weakest {
+            Some((i, victim, priority)) if priority < new_priority => {
+                if let Ok((_, victim_rr, _, _)) = live_one_shots.get(victim) {
+                    victim_rr.stop();
+                }
+                live[i] = (e, new_priority);
+                debug!(
+                    "One-shot {} stopped to make room in category '{}'",
+                    RrObjDesc::new(Some(victim), None),
+                    category.name
+                );
+            }
+            _ => {
+                rr.auto_post = false;
+                debug!(
+                    "One-shot {} won't play: category '{}' is full",
+                    RrObjDesc::new(Some(e), None),
+                    category.name
+                );
+            }
+        }
+    }
+}
+
+fn priority(category: &RrCategory, offset: Option<&RrPriority>) -> i16 {
+    category.priority as i16 + offset.map_or(0, |offset| offset.0 as i16)
+}
@@ -0,0 +1,140 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::set_position;
+use crate::emitter_listener::{ak_id_of, RrEmitter, RrObjDesc, RrRegistered};
+use crate::plugin::CallbackChannel;
+use crate::ToAkTransform;
+use bevy::prelude::*;
+#[cfg(feature = "render")]
+use bevy::render::primitives::{Frustum, Sphere};
+
+#[derive(Debug, Clone, Component)]
+/// Marks the emitter on the same entity as purely cosmetic: it only posts its event and updates
+/// its position while [RrCosmeticActivation] considers it active.
+///
+/// An auto-posting cosmetic emitter spawned while inactive posts its event once it becomes active.
+/// Becoming inactive doesn't stop what is already playing.
+pub struct RrCosmetic {
+    /// Radius of the bounding sphere tested against camera frustums.
+    ///
+    /// Defaults to `0.`.
+    pub radius: f32,
+
+    active: bool,
+    pending_post: bool,
+}
+
+impl Default for RrCosmetic {
+    fn default() -> Self {
+        Self {
+            radius: 0.,
+            active: true,
+            pending_post: false,
+        }
+    }
+}
+
+impl RrCosmetic {
+    pub fn with_radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Whether this emitter was active during the last evaluation of the [RrCosmeticActivation].
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+#[derive(Default, Resource)]
+/// Decides which [RrCosmetic] emitters are active; evaluated every frame.
+///
+/// ### Example
+/// ```rust,ignore
+/// app.insert_resource(RrCosmeticActivation::Predicate(Box::new(|tfm| {
+///     tfm.translation().y > 0.
+/// })));
+/// ```
+pub enum RrCosmeticActivation {
+    /// All cosmetic emitters are active.
+    #[default]
+    Always,
+
+    /// Cosmetic emitters are active while their bounding sphere intersects the frustum of an
+    /// active camera.
+    ///
+    /// Requires the `render` feature.
+    #[cfg(feature = "render")]
+    CameraFrustum,
+
+    /// Cosmetic emitters are active while this returns `true` for their transform.
+    Predicate(Box<dyn Fn(&GlobalTransform) -> bool + Send + Sync>),
+}
+
+// Runs before the registration of new emitters, so that inactive one-shots don't get posted
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn update_cosmetic_emitters(
+    activation: Res<RrCosmeticActivation>,
+    mut emitters: Query<(
+        &mut RrEmitter,
+        &mut RrCosmetic,
+        &GlobalTransform,
+        Option<&RrRegistered>,
+    )>,
+    #[cfg(feature = "render")] cameras: Query<(&Camera, &Frustum)>,
+    cb_channel: Res<CallbackChannel>,
+) {
+    for (mut rr, mut cosmetic, tfm, registered) in emitters.iter_mut() {
+        let active = match &*activation {
+            RrCosmeticActivation::Always => true,
+            #[cfg(feature = "render")]
+            RrCosmeticActivation::CameraFrustum => {
+                let sphere = Sphere {
+                    center: tfm.translation_vec3a(),
+                    radius: cosmetic.radius,
+                };
+                cameras.iter().any(|(camera, frustum)| {
+                    camera.is_active && frustum.intersects_sphere(&sphere, true)
+                })
+            }
+            RrCosmeticActivation::Predicate(predicate) => predicate(tfm),
+        };
+
+        let was_active = cosmetic.active;
+        cosmetic.active = active;
+
+        if registered.is_none() {
+            if !active && rr.auto_post {
+                rr.auto_post = false;
+                cosmetic.pending_post = true;
+            } else if active && cosmetic.pending_post {
+                rr.auto_post = true;
+                cosmetic.pending_post = false;
+            }
+            continue;
+        }
+
+        if !active || was_active {
+            continue;
+        }
+
+        // Its position wasn't updated while inactive
+        if let Some(e) = rr.entity {
+            if let Err(akr) = set_position(ak_id_of(e), tfm.to_ak_transform()) {
+                error!(
+                    "Couldn't update cosmetic emitter {} position - {}",
+                    RrObjDesc::new(Some(e), None),
+                    akr
+                );
+            }
+        }
+
+        if cosmetic.pending_post {
+            cosmetic.pending_post = false;
+            rr.post_associated_event(Some(cb_channel.clone()));
+        }
+    }
+}
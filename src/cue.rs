@@ -0,0 +1,296 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::count_set_rtpc;
+use crate::emitter_listener::{ak_id_of, RrEmitter, RrObjDesc, RrRegistered, RrVariation};
+use crate::plugin::CallbackChannel;
+use crate::sound_engine::get_id_from_string;
+use bevy::asset::{AssetLoader, BoxedFuture, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::HashSet;
+use rrise::game_syncs::SetRtpcValue;
+use rrise::{AkCallbackType, AkID, AkPlayingID, AkRtpcValue, AK_INVALID_PLAYING_ID};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "55495236-b9d4-4044-8d16-1c070f5c9fdc"]
+/// Audio behavior of an emitter, authored as data in a `.cue.ron` file.
+///
+/// Reference it from an entity with an [RrCue] to configure the [RrEmitter] of this entity; the
+/// same cue can be shared by as many entities as needed.
+///
+/// ### Example
+/// ```ron
+/// (
+///     event: "Play_Footstep",
+///     flags: ["EndOfEvent", "Marker"],
+///     rtpc_presets: [("Surface", 2.)],
+///     variation: Some((pitch_cents: (-100., 100.), volume_db: (-3., 0.))),
+///     cooldown: 0.15,
+/// )
+/// ```
+pub struct RrAudioCue {
+    /// Name of the event posted by the emitter.
+    pub event: String,
+
+    /// Callbacks to subscribe to, named after the [AkCallbackType] constants without their `AK_`
+    /// prefix (eg, `EndOfEvent`).
+    #[serde(default)]
+    pub flags: Vec<String>,
+
+    /// RTPC values set on the emitter before its first post.
+    #[serde(default)]
+    pub rtpc_presets: Vec<(String, AkRtpcValue)>,
+
+    #[serde(default)]
+    pub variation: Option<RrCueVariation>,
+
+    /// Minimum delay, in seconds, between two posts with [RrCue::post()].
+    #[serde(default)]
+    pub cooldown: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+/// Ranges of an [RrVariation], using its default RTPCs.
+pub struct RrCueVariation {
+    /// Pitch offset range, in cents.
+    pub pitch_cents: (f32, f32),
+
+    /// Volume offset range, in dB.
+    pub volume_db: (f32, f32),
+}
+
+impl RrAudioCue {
+    /// Callback mask described by [Self::flags]; unknown flags are logged and skipped.
+    pub fn callback_flags(&self) -> AkCallbackType {
+        let mut flags = AkCallbackType(0);
+        for name in self.flags.iter() {
+            match callback_flag(name) {
+                Some(flag) => flags |= flag,
+                None => warn!("Unknown callback flag '{}' in cue '{}'", name, self.event),
+            }
+        }
+        flags
+    }
+
+    /// Configures `rr` to behave as described by this cue.
+    ///
+    /// Names are hashed to IDs, as [RrEmitter] only holds `'static` names.
+    pub fn apply(&self, rr: &mut RrEmitter) {
+        rr.event_id = AkID::ID(get_id_from_string(&self.event));
+        rr.flags = self.callback_flags();
+        rr.rtpc_presets = self
+            .rtpc_presets
+            .iter()
+            .map(|(rtpc, value)| (AkID::ID(get_id_from_string(rtpc)), *value))
+            .collect();
+        rr.variation = self.variation.as_ref().map(|variation| {
+            RrVariation::new(
+                variation.pitch_cents.0..variation.pitch_cents.1,
+                variation.volume_db.0..variation.volume_db.1,
+            )
+        });
+    }
+}
+
+pub(crate) fn callback_flag(name: &str) -> Option<AkCallbackType> {
+    Some(match name {
+        "EndOfEvent" => AkCallbackType::AK_EndOfEvent,
+        "EndOfDynamicSequenceItem" => AkCallbackType::AK_EndOfDynamicSequenceItem,
+        "Marker" => AkCallbackType::AK_Marker,
+        "Duration" => AkCallbackType::AK_Duration,
+        "SpeakerVolumeMatrix" => AkCallbackType::AK_SpeakerVolumeMatrix,
+        "Starvation" => AkCallbackType::AK_Starvation,
+        "MusicPlaylistSelect" => AkCallbackType::AK_MusicPlaylistSelect,
+        "MusicPlayStarted" => AkCallbackType::AK_MusicPlayStarted,
+        "MusicSyncBeat" => AkCallbackType::AK_MusicSyncBeat,
+        "MusicSyncBar" => AkCallbackType::AK_MusicSyncBar,
+        "MusicSyncEntry" => AkCallbackType::AK_MusicSyncEntry,
+        "MusicSyncExit" => AkCallbackType::AK_MusicSyncExit,
+        "MusicSyncGrid" => AkCallbackType::AK_MusicSyncGrid,
+        "MusicSyncUserCue" => AkCallbackType::AK_MusicSyncUserCue,
+        "MusicSyncPoint" => AkCallbackType::AK_MusicSyncPoint,
+        "MusicSyncAll" => AkCallbackType::AK_MusicSyncAll,
+        "MIDIEvent" => AkCallbackType::AK_MIDIEvent,
+        "EnableGetSourcePlayPosition" => AkCallbackType::AK_EnableGetSourcePlayPosition,
+        "EnableGetMusicPlayPosition" => AkCallbackType::AK_EnableGetMusicPlayPosition,
+        "EnableGetSourceStreamBuffering" => AkCallbackType::AK_EnableGetSourceStreamBuffering,
+        _ => return None,
+    })
+}
+
+#[derive(Default)]
+/// Loads [RrAudioCue]s from `.cue.ron` files.
+pub struct RrAudioCueLoader;
+
+impl AssetLoader for RrAudioCueLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let cue = ron::de::from_bytes::<RrAudioCue>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(cue));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cue.ron"]
+    }
+}
+
+#[derive(Debug, Clone, Component)]
+/// Configures the [RrEmitter] of the same entity from an [RrAudioCue].
+///
+/// The cue is applied as soon as it is loaded. If the emitter auto posts, its post is held until
+/// then.
+///
+/// When the cue file gets hot-reloaded (see
+/// [`AssetPlugin::watch_for_changes`](bevy::asset::AssetPlugin::watch_for_changes)), it is applied
+/// again. A changed event is then handled by the
+/// [`on_event_change`](RrEmitter::on_event_change) policy of the emitter, unless
+/// [Self::restart_on_reload] is set.
+///
+/// ### Example
+/// ```rust,ignore
+/// commands
+///     .spawn(RrEmitterBundle::new(Vec3::ZERO))
+///     .insert(RrCue::new(asset_server.load("audio/footstep.cue.ron")));
+/// ```
+pub struct RrCue {
+    pub handle: Handle<RrAudioCue>,
+
+    /// Whether to stop and post the event again on the emitter when the cue gets hot-reloaded, if
+    /// it was playing.
+    ///
+    /// Useful for looping events. Defaults to `false`.
+    pub restart_on_reload: bool,
+
+    applied: bool,
+    pending_post: bool,
+    last_post: Option<f64>,
+}
+
+impl RrCue {
+    pub fn new(handle: Handle<RrAudioCue>) -> Self {
+        Self {
+            handle,
+            restart_on_reload: false,
+            applied: false,
+            pending_post: false,
+            last_post: None,
+        }
+    }
+
+    pub fn with_restart_on_reload(mut self, restart: bool) -> Self {
+        self.restart_on_reload = restart;
+        self
+    }
+
+    /// Whether the cue got applied on the emitter.
+    pub fn is_applied(&self) -> bool {
+        self.applied
+    }
+
+    /// Posts the event of the cue on `rr`, unless the cue isn't applied yet or is cooling down.
+    ///
+    /// Returns [AK_INVALID_PLAYING_ID] when nothing got posted.
+    pub fn post(
+        &mut self,
+        rr: &mut RrEmitter,
+        cues: &Assets<RrAudioCue>,
+        time: &Time,
+        cb_channel: Option<CallbackChannel>,
+    ) -> AkPlayingID {
+        let Some(cue) = cues.get(&self.handle).filter(|_| self.applied) else {
+            return AK_INVALID_PLAYING_ID;
+        };
+
+        let now = time.elapsed_seconds_f64();
+        if let Some(last_post) = self.last_post {
+            if now - last_post < cue.cooldown {
+                debug!("Cue '{}' is cooling down", cue.event);
+                return AK_INVALID_PLAYING_ID;
+            }
+        }
+
+        self.last_post = Some(now);
+        rr.post_associated_event(cb_channel)
+    }
+}
+
+// Runs before the registration of new emitters, so that auto posts use the event of their cue
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn apply_audio_cues(
+    mut emitters: Query<(Entity, &mut RrEmitter, &mut RrCue, Option<&RrRegistered>)>,
+    cues: Res<Assets<RrAudioCue>>,
+    mut cue_events: EventReader<AssetEvent<RrAudioCue>>,
+    cb_channel: Res<CallbackChannel>,
+) {
+    let reloaded: HashSet<_> = cue_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle.id()),
+            _ => None,
+        })
+        .collect();
+
+    for (e, mut rr, mut rr_cue, registered) in emitters.iter_mut() {
+        let reload = rr_cue.applied && reloaded.contains(&rr_cue.handle.id());
+        if rr_cue.applied && !reload {
+            continue;
+        }
+
+        let Some(cue) = cues.get(&rr_cue.handle) else {
+            if registered.is_none() && rr.auto_post {
+                rr.auto_post = false;
+                rr_cue.pending_post = true;
+            }
+            continue;
+        };
+
+        cue.apply(&mut rr);
+        rr_cue.applied = true;
+        debug!(
+            "Cue '{}' {} on {}",
+            cue.event,
+            if reload { "reloaded" } else { "applied" },
+            RrObjDesc::new(Some(e), None)
+        );
+
+        if registered.is_none() {
+            rr.auto_post |= rr_cue.pending_post;
+            rr_cue.pending_post = false;
+            continue;
+        }
+
+        // Too late for the emitter to set them at registration
+        let id = ak_id_of(e);
+        for &(rtpc, value) in rr.rtpc_presets.iter() {
+            count_set_rtpc();
+            if let Err(akr) = SetRtpcValue::new(rtpc, value).for_target(id).set() {
+                error!(
+                    "Couldn't set RTPC '{}' on {} - {}",
+                    rtpc,
+                    RrObjDesc::new(Some(e), None),
+                    akr
+                );
+            }
+        }
+
+        if reload && rr_cue.restart_on_reload {
+            rr.acknowledge_event_change();
+            if rr.is_playing() {
+                rr.stop();
+                rr.post_associated_event(Some(cb_channel.clone()));
+            }
+        } else if rr_cue.pending_post {
+            rr_cue.pending_post = false;
+            rr.post_associated_event(Some(cb_channel.clone()));
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::{ak_id_of, RrEmitter, RrListener, RrObjDesc, RrRegistered};
+use bevy::prelude::*;
+use rrise::sound_engine::unregister_game_obj;
+
+#[derive(Debug, Clone, Component)]
+/// Keeps the emitter on the same entity unregistered from Wwise while it is farther than
+/// [Self::radius] from all listeners, so that levels with hundreds of ambient emitters only
+/// spend game objects on those that can be heard.
+///
+/// An emitter spawned out of range gets registered, and auto posts its event, once a listener
+/// comes in range. An emitter leaving the range gets its events stopped and is unregistered; it
+/// posts its auto post event again when back in range. While culled,
+/// [RrEmitter::is_registered()] is `false` and posting on it fails.
+///
+/// A good radius is the maximum attenuation distance of the event; see
+/// [RrSoundbanksInfo::max_attenuation_distance()](crate::soundbanks_info::RrSoundbanksInfo::max_attenuation_distance()).
+///
+/// *Remark* Emitters on listener entities are never culled.
+///
+/// ### Example
+/// ```rust,ignore
+/// commands.spawn((
+///     RrEmitterBundle::new(Vec3::new(200., 0., 0.)).with_event("Play_Waterfall", false),
+///     RrDistanceCulling::new(50.).with_hysteresis(5.),
+/// ));
+/// ```
+pub struct RrDistanceCulling {
+    pub radius: f32,
+
+    /// Extra distance a registered emitter must get past [Self::radius] to be culled, so that
+    /// listeners hovering at the boundary don't register and unregister it each frame.
+    ///
+    /// Defaults to `0.`.
+    pub hysteresis: f32,
+
+    culled: bool,
+}
+
+impl RrDistanceCulling {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            hysteresis: 0.,
+            culled: false,
+        }
+    }
+
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Whether the emitter is kept unregistered because no listener is in range.
+    pub fn is_culled(&self) -> bool {
+        self.culled
+    }
+}
+
+// Runs before the registration of new emitters, so that those out of range don't get registered
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn update_distance_culling(
+    mut commands: Commands,
+    listeners: Query<&GlobalTransform, With<RrListener>>,
+    mut emitters: Query<
+        (
+            Entity,
+            &mut RrEmitter,
+            &mut RrDistanceCulling,
+            &GlobalTransform,
+            Option<&RrRegistered>,
+        ),
+        Without<RrListener>,
+    >,
+) {
+    for (e, mut rr_e, mut culling, tfm, registered) in emitters.iter_mut() {
+        let pos = tfm.translation();
+        let distance = listeners
+            .iter()
+            .map(|l_tfm| l_tfm.translation().distance(pos))
+            .fold(f32::INFINITY, f32::min);
+        let culled = if registered.is_some() {
+            distance > culling.radius + culling.hysteresis
+        } else {
+            distance > culling.radius
+        };
+
+        if culled == culling.culled {
+            continue;
+        }
+        culling.culled = culled;
+
+        if !culled {
+            // Picked up by init_new_rr_objects()
+            debug!("{} came in range", RrObjDesc::new(Some(e), None));
+            continue;
+        }
+
+        if registered.is_some() {
+            rr_e.stop();
+            if let Err(akr) = unregister_game_obj(ak_id_of(e)) {
+                error!(
+                    "Couldn't unregister culled {} - {}",
+                    RrObjDesc::new(Some(e), None),
+                    akr
+                );
+            }
+            commands.entity(e).remove::<RrRegistered>();
+
+            // Set again by init_new_rr_objects() once back in range
+            rr_e.entity = None;
+        }
+        debug!("{} culled", RrObjDesc::new(Some(e), None));
+    }
+}
@@ -0,0 +1,230 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+#[cfg(debug_assertions)]
+use crate::emitter_listener::{RrEmitter, RrLoopingAmbience, RrObjDesc, RrRegistered};
+#[cfg(debug_assertions)]
+use crate::plugin::CallbackChannel;
+#[cfg(debug_assertions)]
+use crate::voice_queue::RrVoiceQueue;
+use bevy::diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy::prelude::*;
+#[cfg(debug_assertions)]
+use bevy::utils::HashSet;
+use rrise::{AkGameObjectID, AkResult, AkSoundPosition};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static POSTS: AtomicU64 = AtomicU64::new(0);
+static SET_POSITIONS: AtomicU64 = AtomicU64::new(0);
+static SET_RTPCS: AtomicU64 = AtomicU64::new(0);
+
+/// Adds per-frame counts of the Wwise API calls made by bevy-rrise to Bevy [Diagnostics], and
+/// records them as a `trace` level tracing event every frame.
+///
+/// Works along [RrisePlugin](crate::plugin::RrisePlugin); add it like any other Bevy diagnostics
+/// plugin.
+#[derive(Default)]
+pub struct RriseDiagnosticsPlugin;
+
+impl RriseDiagnosticsPlugin {
+    /// Events posted during the frame.
+    pub const POSTS: DiagnosticId =
+        DiagnosticId::from_u128(0x3b1f_5d3e_9c62_4d1b_a8f0_6e2c_7b45_0001);
+
+    /// Positions set during the frame.
+    pub const SET_POSITIONS: DiagnosticId =
+        DiagnosticId::from_u128(0x3b1f_5d3e_9c62_4d1b_a8f0_6e2c_7b45_0002);
+
+    /// RTPC values set during the frame.
+    pub const SET_RTPCS: DiagnosticId =
+        DiagnosticId::from_u128(0x3b1f_5d3e_9c62_4d1b_a8f0_6e2c_7b45_0003);
+
+    fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::POSTS, "rrise_posts", 20));
+        diagnostics.add(Diagnostic::new(
+            Self::SET_POSITIONS,
+            "rrise_set_positions",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(Self::SET_RTPCS, "rrise_set_rtpcs", 20));
+    }
+
+    fn diagnostic_system(mut diagnostics: ResMut<Diagnostics>) {
+        let posts = POSTS.swap(0, Ordering::Relaxed);
+        let set_positions = SET_POSITIONS.swap(0, Ordering::Relaxed);
+        let set_rtpcs = SET_RTPCS.swap(0, Ordering::Relaxed);
+
+        diagnostics.add_measurement(Self::POSTS, || posts as f64);
+        diagnostics.add_measurement(Self::SET_POSITIONS, || set_positions as f64);
+        diagnostics.add_measurement(Self::SET_RTPCS, || set_rtpcs as f64);
+
+        trace!(
+            posts,
+            set_positions,
+            set_rtpcs,
+            "Wwise API calls this frame"
+        );
+    }
+}
+
+impl Plugin for RriseDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .add_system_to_stage(CoreStage::Last, Self::diagnostic_system);
+    }
+}
+
+pub(crate) fn count_post() {
+    POSTS.fetch_add(1, Ordering::Relaxed);
+}
+
+pub(crate) fn count_set_rtpc() {
+    SET_RTPCS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counted version of [rrise::sound_engine::set_position()].
+pub(crate) fn set_position<T: Into<AkSoundPosition>>(
+    game_obj_id: AkGameObjectID,
+    position: T,
+) -> Result<(), AkResult> {
+    SET_POSITIONS.fetch_add(1, Ordering::Relaxed);
+    rrise::sound_engine::set_position(game_obj_id, position)
+}
+
+#[cfg(debug_assertions)]
+#[derive(Default)]
+pub(crate) struct GrowthTracker {
+    next_sample: f64,
+    sizes: [usize; GrowthTracker::COLLECTIONS],
+    growing_for: [u32; GrowthTracker::COLLECTIONS],
+}
+
+/// Debug builds only: samples the size of the plugin's internal collections every minute, and
+/// warns when one of them kept growing for [GrowthTracker::SAMPLES] samples in a row; this is
+/// most likely a leak and would hurt long sessions.
+#[cfg(debug_assertions)]
+pub(crate) fn report_growing_collections(
+    emitters: Query<&RrEmitter>,
+    registered: Query<(), (With<RrEmitter>, With<RrRegistered>)>,
+    cb_channel: Res<CallbackChannel>,
+    voice_queue: Res<RrVoiceQueue>,
+    time: Res<Time>,
+    mut tracker: Local<GrowthTracker>,
+) {
+    const NAMES: [&str; GrowthTracker::COLLECTIONS] = [
+        "emitter playing IDs",
+        "callback closures",
+        "pending callbacks",
+        "callback subscriptions",
+        "subscribed playing IDs",
+        "pending bank events",
+        "deferred calls",
+        "voice queue lines",
+        "registered emitters",
+    ];
+
+    let now = time.elapsed_seconds_f64();
+    if now < tracker.next_sample {
+        return;
+    }
+    tracker.next_sample = now + GrowthTracker::PERIOD;
+
+    let sizes = [
+        emitters
+            .iter()
+            .map(|rr| rr.playing_events.read().unwrap().len())
+            .sum(),
+        cb_channel.closures.lock().unwrap().len(),
+        cb_channel.pending_callbacks(),
+        cb_channel.subscriptions(),
+        cb_channel.tracked_playing_ids(),
+        crate::sound_engine::pending_bank_events(),
+        crate::sound_engine::pending_deferred_calls(),
+        voice_queue.len(),
+        registered.iter().len(),
+    ];
+
+    let tracker = &mut *tracker;
+    for (i, size) in sizes.into_iter().enumerate() {
+        if size > tracker.sizes[i] {
+            tracker.growing_for[i] += 1;
+        } else {
+            tracker.growing_for[i] = 0;
+        }
+        tracker.sizes[i] = size;
+
+        if tracker.growing_for[i] >= GrowthTracker::SAMPLES {
+            warn!(
+                "bevy-rrise internal collection '{}' kept growing for the last {} minutes ({} items); please report this leak",
+                NAMES[i],
+                GrowthTracker::SAMPLES,
+                size
+            );
+            tracker.growing_for[i] = 0;
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl GrowthTracker {
+    const COLLECTIONS: usize = 9;
+    const PERIOD: f64 = 60.;
+    const SAMPLES: u32 = 10;
+}
+
+/// Debug builds only: warns about events playing far longer than their estimated duration,
+/// which usually are leaked loops or loops whose stop event never got posted.
+///
+/// Needs [RriseBasicSettings::track_durations](crate::plugin::RriseBasicSettings::track_durations).
+/// Emitters with an [RrLoopingAmbience] are expected to loop forever and aren't watched.
+#[cfg(debug_assertions)]
+pub(crate) fn watch_never_ending_events(
+    emitters: Query<(Entity, &RrEmitter), Without<RrLoopingAmbience>>,
+    time: Res<Time>,
+    mut next_check: Local<f64>,
+    mut reported: Local<HashSet<rrise::AkPlayingID>>,
+) {
+    const MIN_OVERTIME: f32 = 10.;
+    const DURATION_FACTOR: f32 = 4.;
+
+    let now = time.elapsed_seconds_f64();
+    if now < *next_check {
+        return;
+    }
+    *next_check = now + 1.;
+
+    let mut still_playing = HashSet::default();
+    for (e, rr) in emitters.iter() {
+        for playing in rr.playing_events() {
+            still_playing.insert(playing.playing_id());
+
+            let Some(duration) = playing.estimated_duration() else {
+                continue;
+            };
+            let duration = duration.as_secs_f32();
+            let elapsed = playing.elapsed().as_secs_f32();
+            if elapsed < (duration * DURATION_FACTOR).max(duration + MIN_OVERTIME)
+                || !reported.insert(playing.playing_id())
+            {
+                continue;
+            }
+
+            warn!(
+                "Event '{}' (playing ID {}) on {} has been playing for {:.0}s but should last {:.1}s; \
+                is it a leaked loop, or a loop whose stop event never got posted?",
+                playing
+                    .event_name
+                    .clone()
+                    .unwrap_or_else(|| playing.event_id().to_string()),
+                playing.playing_id(),
+                RrObjDesc::new(Some(e), rr.name.as_deref()),
+                elapsed,
+                duration
+            );
+        }
+    }
+
+    // Only remember playing IDs still alive
+    reported.retain(|playing_id| still_playing.contains(playing_id));
+}
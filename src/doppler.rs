@@ -0,0 +1,233 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::count_set_rtpc;
+use crate::emitter_listener::{ak_id_of, RrEmitter, RrListener, RrObjDesc, RrRegistered};
+use bevy::prelude::*;
+use rrise::game_syncs::SetRtpcValue;
+use rrise::AkID;
+
+#[derive(Debug, Clone, Resource)]
+/// How the Doppler factor of [RrDoppler] emitters is computed.
+pub struct RrDopplerSettings {
+    /// In world units per second.
+    ///
+    /// Defaults to `340.`, the speed of sound in air for a world in meters.
+    pub speed_of_sound: f32,
+
+    /// The factor is clamped to `min_factor..=max_factor`; supersonic emitters get `max_factor`.
+    ///
+    /// Defaults to `1. / 16.`.
+    pub min_factor: f32,
+
+    /// Defaults to `16.`.
+    pub max_factor: f32,
+
+    /// RTPC set to the Doppler factor on each emitter; in your Wwise project, map it to the pitch
+    /// of the events to shift (`1.` means no shift, `2.` one octave up).
+    ///
+    /// Defaults to `Rrise_Doppler`.
+    pub rtpc: AkID<'static>,
+}
+
+impl Default for RrDopplerSettings {
+    fn default() -> Self {
+        Self {
+            speed_of_sound: 340.,
+            min_factor: 1. / 16.,
+            max_factor: 16.,
+            rtpc: AkID::Name("Rrise_Doppler"),
+        }
+    }
+}
+
+impl RrDopplerSettings {
+    /// Doppler factor heard by a listener at `listener` moving at `listener_velocity`, from an
+    /// emitter at `emitter` moving at `emitter_velocity`.
+    pub fn factor(
+        &self,
+        emitter: Vec3,
+        emitter_velocity: Vec3,
+        listener: Vec3,
+        listener_velocity: Vec3,
+    ) -> f32 {
+        // Unit vector from the listener to the emitter; speeds along it move them apart
+        let Some(dir) = (emitter - listener).try_normalize() else {
+            return 1.;
+        };
+
+        let source_speed = emitter_velocity.dot(dir);
+        let listener_speed = listener_velocity.dot(dir);
+
+        let denominator = self.speed_of_sound + source_speed;
+        if denominator <= 0. {
+            return self.max_factor;
+        }
+        ((self.speed_of_sound + listener_speed) / denominator)
+            .clamp(self.min_factor, self.max_factor)
+    }
+}
+
+#[derive(Debug, Clone, Default, Component)]
+/// Velocity of a registered emitter or listener, derived from its global transforms between
+/// frames.
+///
+/// bevy-rrise adds it to all registered objects and updates it at the end of each frame, after
+/// transform propagation.
+pub struct RrVelocity {
+    linear: Vec3,
+    last_position: Option<Vec3>,
+}
+
+impl RrVelocity {
+    /// In world units per second.
+    pub fn linear(&self) -> Vec3 {
+        self.linear
+    }
+
+    /// Forgets the last position, so that the next update reports no velocity.
+    ///
+    /// Call it when teleporting the object, so that the jump isn't taken for a very fast move.
+    pub fn reset(&mut self) {
+        self.linear = Vec3::ZERO;
+        self.last_position = None;
+    }
+}
+
+#[derive(Debug, Clone, Default, Component)]
+/// Marks the emitter on the same entity for Doppler effect: each frame, the Doppler factor
+/// between it and the closest default listener is computed from their [RrVelocity] and set on the
+/// emitter for [RrDopplerSettings::rtpc].
+///
+/// The RTPC value is shared by all the listeners of the emitter, so the closest one, which
+/// dominates the mix, is used.
+pub struct RrDoppler {
+    factor: f32,
+    listener: Option<Entity>,
+}
+
+impl RrDoppler {
+    /// Doppler factor computed last frame.
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+
+    /// Listener the Doppler factor was computed for last frame.
+    pub fn listener(&self) -> Option<Entity> {
+        self.listener
+    }
+}
+
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn track_rr_velocities(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tracked: Query<(&mut RrVelocity, &GlobalTransform), With<RrRegistered>>,
+    untracked: Query<(Entity, &GlobalTransform), (With<RrRegistered>, Without<RrVelocity>)>,
+) {
+    let dt = time.delta_seconds();
+    for (mut velocity, tfm) in tracked.iter_mut() {
+        let pos = tfm.translation();
+        velocity.linear = match velocity.last_position {
+            Some(last_position) if dt > 0. => (pos - last_position) / dt,
+            _ => Vec3::ZERO,
+        };
+        velocity.last_position = Some(pos);
+    }
+
+    for (e, tfm) in untracked.iter() {
+        commands.entity(e).insert(RrVelocity {
+            linear: Vec3::ZERO,
+            last_position: Some(tfm.translation()),
+        });
+    }
+}
+
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn update_doppler(
+    settings: Res<RrDopplerSettings>,
+    listeners: Query<(Entity, &RrListener, &GlobalTransform, &RrVelocity), With<RrRegistered>>,
+    mut emitters: Query<
+        (Entity, &mut RrDoppler, &GlobalTransform, &RrVelocity),
+        (With<RrEmitter>, With<RrRegistered>),
+    >,
+) {
+    for (e, mut doppler, tfm, velocity) in emitters.iter_mut() {
+        let pos = tfm.translation();
+        let Some((listener, listener_tfm, listener_velocity)) = listeners
+            .iter()
+            .filter(|(_, rr_l, _, _)| rr_l.is_default())
+            .map(|(l, _, l_tfm, l_velocity)| (l, l_tfm.translation(), l_velocity.linear()))
+            .min_by(|(_, a, _), (_, b, _)| {
+                a.distance_squared(pos).total_cmp(&b.distance_squared(pos))
+            })
+        else {
+            continue;
+        };
+
+        doppler.listener = Some(listener);
+        doppler.factor = settings.factor(pos, velocity.linear(), listener_tfm, listener_velocity);
+
+        count_set_rtpc();
+        if let Err(akr) = SetRtpcValue::new(settings.rtpc, doppler.factor)
+            .for_target(ak_id_of(e))
+            .set()
+        {
+            error!(
+                "Couldn't set RTPC '{}' on {} - {}",
+                settings.rtpc,
+                RrObjDesc::new(Some(e), None),
+                akr
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-5;
+
+    #[test]
+    fn approaching_source() {
+        let settings = RrDopplerSettings::default();
+        let c = settings.speed_of_sound;
+        let v = 40.;
+
+        let factor = settings.factor(Vec3::X * 100., Vec3::NEG_X * v, Vec3::ZERO, Vec3::ZERO);
+        assert!((factor - c / (c - v)).abs() < EPSILON);
+        assert!(factor > 1.);
+    }
+
+    #[test]
+    fn receding_source() {
+        let settings = RrDopplerSettings::default();
+        let c = settings.speed_of_sound;
+        let v = 40.;
+
+        let factor = settings.factor(Vec3::X * 100., Vec3::X * v, Vec3::ZERO, Vec3::ZERO);
+        assert!((factor - c / (c + v)).abs() < EPSILON);
+        assert!(factor < 1.);
+    }
+
+    #[test]
+    fn supersonic_source() {
+        let settings = RrDopplerSettings::default();
+        let v = settings.speed_of_sound * 2.;
+
+        let factor = settings.factor(Vec3::X * 100., Vec3::NEG_X * v, Vec3::ZERO, Vec3::ZERO);
+        assert_eq!(factor, settings.max_factor);
+    }
+
+    #[test]
+    fn co_located() {
+        let settings = RrDopplerSettings::default();
+
+        let factor = settings.factor(Vec3::ONE, Vec3::NEG_X * 40., Vec3::ONE, Vec3::X * 40.);
+        assert_eq!(factor, 1.);
+    }
+}
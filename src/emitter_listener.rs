@@ -2,24 +2,47 @@
  * Copyright (c) 2022 Contributors to the bevy-rrise project
  */
 
+use crate::cosmetic::RrCosmetic;
+use crate::culling::RrDistanceCulling;
+use crate::diagnostics::{count_post, count_set_rtpc, set_position};
 use crate::plugin::CallbackChannel;
-use crate::ToAkTransform;
+use crate::sound_engine::get_id_from_string;
+use crate::switch::RrSwitch;
+use crate::warm_start::record_post;
+use crate::{AkCallbackEvent, ToAkTransform};
+use bevy::ecs::entity::Entities;
+use bevy::ecs::system::SystemParam;
 use bevy::math::Affine3A;
 use bevy::prelude::*;
-#[cfg(wwrelease)]
+use bevy::utils::{HashMap, HashSet};
+use rrise::game_syncs::{set_switch, SetRtpcValue};
+#[cfg(any(wwrelease, feature = "strip-names"))]
 use rrise::sound_engine::register_game_obj;
-#[cfg(not(wwrelease))]
+#[cfg(not(any(wwrelease, feature = "strip-names")))]
 use rrise::sound_engine::register_named_game_obj;
-use rrise::sound_engine::{add_default_listener, set_position, stop_all, PostEvent};
+use rrise::sound_engine::{
+    add_default_listener, remove_default_listener, stop_all, unregister_game_obj, PostEvent,
+};
 use rrise::{
-    AkCallbackInfo, AkCallbackType, AkGameObjectID, AkID, AkPlayingID, AkResult,
-    AK_INVALID_PLAYING_ID,
+    AkCallbackInfo, AkCallbackType, AkGameObjectID, AkID, AkPlayingID, AkResult, AkRtpcValue,
+    AkUniqueID, AK_INVALID_PLAYING_ID,
 };
-use std::sync::{Arc, RwLock};
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 use tracing;
 
 #[derive(Component)]
-/// Marker for emitters that are registered in Wwise.
+/// Marker for entities whose Wwise game object is registered.
+///
+/// An entity maps to a single Wwise game object, shared by all the bevy-rrise components on it.
+/// The game object gets registered (and named) once, by the [RrListener] if there is one,
+/// otherwise by the [RrEmitter]; the other components only apply their own setup to it.
 ///
 /// A [RrEmitter] sitting on the same entity than this is guaranteed to be registered.
 pub struct RrRegistered;
@@ -28,7 +51,8 @@ pub struct RrRegistered;
 /// Sound emitter configuration.
 ///
 /// If its entity gets destroyed or this component gets removed, the events posted with it will be
-/// stopped.
+/// stopped, unless [`stop_on_destroy`](RrEmitter::stop_on_destroy) is `false`, and its Wwise game
+/// object is unregistered.
 pub struct RrEmitter {
     /// The event to pre-set on this emitter.
     /// Defaults to no event (ie, `""`).
@@ -50,9 +74,104 @@ pub struct RrEmitter {
     /// *Remark* "Done playing" = no more events are playing on it - work if several events got posted
     /// simultaneously with it.
     pub despawn_on_silent: bool,
-    // pub stop_on_destroy: bool, // TODO
-    pub(crate) playing_ids: Arc<RwLock<Vec<AkPlayingID>>>,
+
+    /// RTPC values to set on this emitter right after it gets registered, before any auto post.
+    pub rtpc_presets: Vec<(AkID<'static>, AkRtpcValue)>,
+
+    /// Switch values (`(group, state)`) to set on this emitter right after it gets registered,
+    /// before any auto post.
+    ///
+    /// For each pair, `group` and `state` should both be names or both be IDs.
+    pub switch_presets: Vec<(AkID<'static>, AkID<'static>)>,
+
+    /// Randomized RTPC values to set on this emitter before each post.
+    pub variation: Option<RrVariation>,
+
+    /// What happens when [`event_id`](RrEmitter::event_id) gets changed on a registered emitter.
+    /// Defaults to [RrEventChange::Ignore].
+    pub on_event_change: RrEventChange,
+
+    /// Whether to stop the events playing on this emitter when its entity gets despawned or this
+    /// component gets removed. If `false`, they play out from the last position of the emitter.
+    /// Defaults to `true`.
+    pub stop_on_destroy: bool,
+    pub(crate) playing_events: Arc<RwLock<Vec<RrPlayingEvent>>>,
+    pub(crate) end_wakers: Arc<Mutex<HashMap<AkPlayingID, Waker>>>,
     pub(crate) entity: Option<Entity>,
+    pub(crate) name: Option<String>,
+    known_event_id: AkUniqueID,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// Policy applied by an [RrEmitter] when its [`event_id`](RrEmitter::event_id) is changed after
+/// it got registered.
+pub enum RrEventChange {
+    /// Nothing happens; the new event will be used by the next
+    /// [post_associated_event()](RrEmitter::post_associated_event).
+    #[default]
+    Ignore,
+
+    /// Stops everything playing on the emitter, then posts the new event.
+    StopAndPost,
+
+    /// Posts the new event, letting the previous ones play out.
+    PostAdditional,
+}
+
+#[derive(Debug, Clone)]
+/// Basic pitch and volume variation, for events that weren't authored with randomizers.
+///
+/// Before each post from its [RrEmitter], random values within the ranges are set on the
+/// emitter for [Self::pitch_rtpc] and [Self::volume_rtpc]; in your Wwise project, bind these
+/// RTPCs to the pitch and voice volume of the events to vary.
+pub struct RrVariation {
+    /// Pitch offset range, in cents.
+    pub pitch_cents: Range<f32>,
+
+    /// Volume offset range, in dB.
+    pub volume_db: Range<f32>,
+
+    /// Defaults to `Rrise_PitchVariation`.
+    pub pitch_rtpc: AkID<'static>,
+
+    /// Defaults to `Rrise_VolumeVariation`.
+    pub volume_rtpc: AkID<'static>,
+}
+
+impl RrVariation {
+    pub fn new(pitch_cents: Range<f32>, volume_db: Range<f32>) -> Self {
+        Self {
+            pitch_cents,
+            volume_db,
+            pitch_rtpc: AkID::Name("Rrise_PitchVariation"),
+            volume_rtpc: AkID::Name("Rrise_VolumeVariation"),
+        }
+    }
+
+    pub fn with_rtpcs<T: Into<AkID<'static>>, U: Into<AkID<'static>>>(
+        mut self,
+        pitch_rtpc: T,
+        volume_rtpc: U,
+    ) -> Self {
+        self.pitch_rtpc = pitch_rtpc.into();
+        self.volume_rtpc = volume_rtpc.into();
+        self
+    }
+
+    fn sample(range: &Range<f32>) -> AkRtpcValue {
+        range.start + fastrand::f32() * (range.end - range.start).max(0.)
+    }
+
+    fn apply(&self, id: AkGameObjectID) -> Result<(), AkResult> {
+        count_set_rtpc();
+        SetRtpcValue::new(self.pitch_rtpc, Self::sample(&self.pitch_cents))
+            .for_target(id)
+            .set()?;
+        count_set_rtpc();
+        SetRtpcValue::new(self.volume_rtpc, Self::sample(&self.volume_db))
+            .for_target(id)
+            .set()
+    }
 }
 
 #[derive(Bundle, Default)]
@@ -76,11 +195,138 @@ pub struct RrDynamicEmitterBundle {
     tfm: Transform,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component)]
+/// How the transform of an emitter is interpreted when forwarded to Wwise.
+pub enum RrEmitterSpace {
+    /// The emitter is positioned in the world, like any other entity.
+    #[default]
+    World,
+
+    /// The emitter transform is relative to the first default listener: a sound at `(-100, 0)`
+    /// will always be heard on the left, wherever the listener goes.
+    ///
+    /// Best suited for UI-space sounds and 2D games where the camera moves.
+    ListenerRelative,
+}
+
+#[derive(Bundle)]
+/// 2D sound emitter.
+///
+/// Its position is interpreted in [RrEmitterSpace::ListenerRelative] space by default; use
+/// [Self::in_world_space()] for sounds belonging to the 2D world.
+///
+/// *Remark* [Camera2dBundle]s sit far from the 2D plane on the Z axis; if you attach a listener
+/// to one for world space emitters, offset it back to `z = 0` with
+/// [RrListenerBundle::with_parent_offset()].
+pub struct RrEmitter2dBundle {
+    #[bundle]
+    emitter: RrDynamicEmitterBundle,
+    space: RrEmitterSpace,
+}
+
+impl Default for RrEmitter2dBundle {
+    fn default() -> Self {
+        Self {
+            emitter: default(),
+            space: RrEmitterSpace::ListenerRelative,
+        }
+    }
+}
+
 #[derive(Debug, Component)]
+/// Event that should always be playing on the [RrEmitter] of the same entity.
+///
+/// Whenever its voice dies (soundbank reloaded, voice killed, sound engine restarted...), the
+/// event gets posted again. Failed posts are retried every [Self::RETRY_DELAY] seconds.
+///
+/// This makes hot-reloading soundbanks at dev time seamless for ambiences and music.
+pub struct RrLoopingAmbience {
+    pub event: AkID<'static>,
+    playing_id: AkPlayingID,
+    next_retry: f64,
+}
+
+impl RrLoopingAmbience {
+    /// Delay, in seconds, between two attempts to post the event when posting fails.
+    pub const RETRY_DELAY: f64 = 1.;
+
+    pub fn new<T: Into<AkID<'static>>>(event: T) -> Self {
+        Self {
+            event: event.into(),
+            playing_id: AK_INVALID_PLAYING_ID,
+            next_retry: 0.,
+        }
+    }
+
+    /// Whether the ambience event is currently playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing_id != AK_INVALID_PLAYING_ID
+    }
+}
+
+#[derive(Debug, Clone, Component)]
+/// Despawns its entity (recursively) when a given Wwise callback occurs on an emitter.
+///
+/// Generalizes [RrEmitterBundle::with_event()]'s `despawn_on_silent` to any callback, which lets
+/// you tie the lifetime of any entity (VFX shells, props...) to audio.
+///
+/// *Remark* The watched emitter must request the callback in its flags, and post with a
+/// [CallbackChannel].
+///
+/// ### Example
+/// ```rust,ignore
+/// // Explode when the "explode" marker of the fuse sound is reached
+/// commands.spawn((
+///     RrEmitterBundle::new(pos)
+///         .with_event("PlayFuse", false)
+///         .with_flags(AkCallbackType::AK_Marker),
+///     RrDespawnOn::new(AkCallbackType::AK_Marker).with_label("explode"),
+/// ));
+/// ```
+pub struct RrDespawnOn {
+    /// Callbacks triggering the despawn; any of the flags matches.
+    pub callback_type: AkCallbackType,
+
+    /// If set, only callbacks whose [label](crate::AkCallbackEvent::label()) (marker label or
+    /// music user cue name) is this one trigger the despawn.
+    pub label: Option<String>,
+
+    /// Emitter whose callbacks are watched; if `None`, the emitter of this entity.
+    pub emitter: Option<Entity>,
+}
+
+impl RrDespawnOn {
+    pub fn new(callback_type: AkCallbackType) -> Self {
+        Self {
+            callback_type,
+            label: None,
+            emitter: None,
+        }
+    }
+
+    pub fn with_label<T: Into<String>>(mut self, label: T) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Watches the callbacks of the emitter on `emitter` instead of this entity's.
+    pub fn from_emitter(mut self, emitter: Entity) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+}
+
+#[derive(Debug, Component, Reflect, FromReflect)]
+#[reflect(Component)]
 /// Sound listener marker.
+///
+/// Can be saved in scenes; its entity and name are set again when it gets registered.
 pub struct RrListener {
     is_default: bool,
+    #[reflect(ignore)]
     pub(crate) entity: Option<Entity>,
+    #[reflect(ignore)]
+    pub(crate) name: Option<String>,
 }
 
 impl RrListener {
@@ -101,10 +347,102 @@ impl Default for RrListener {
         Self {
             is_default: true,
             entity: None,
+            name: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Component, Reflect, FromReflect)]
+#[reflect(Component)]
+/// Scene-friendly description of an [RrEmitter].
+///
+/// [RrEmitter] can't be saved in scenes, as Wwise IDs aren't reflectable; put this on entities of
+/// your scenes instead. Once spawned, it gets replaced by the [RrEmitter] it describes, which then
+/// gets registered like any other.
+pub struct RrSceneEmitter {
+    /// Name of the event to pre-set on the emitter.
+    pub event: String,
+
+    /// Callbacks to subscribe to, named after the [AkCallbackType] constants without their `AK_`
+    /// prefix (eg, `EndOfEvent`).
+    pub flags: Vec<String>,
+
+    pub auto_post: bool,
+    pub despawn_on_silent: bool,
+}
+
+impl RrSceneEmitter {
+    /// The [RrEmitter] described by this.
+    ///
+    /// Its event name is hashed to an ID, as [RrEmitter] only holds `'static` names; an empty name
+    /// means no event.
+    pub fn to_emitter(&self) -> RrEmitter {
+        let mut flags = AkCallbackType(0);
+        for name in self.flags.iter() {
+            match crate::cue::callback_flag(name) {
+                Some(flag) => flags |= flag,
+                None => warn!("Unknown callback flag '{}' in scene emitter", name),
+            }
+        }
+
+        let event_id = if self.event.is_empty() {
+            RrEmitter::default().event_id
+        } else {
+            AkID::ID(get_id_from_string(&self.event))
+        };
+
+        RrEmitter {
+            event_id,
+            flags,
+            auto_post: self.auto_post,
+            despawn_on_silent: self.despawn_on_silent,
+            ..default()
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// When an [RrTemporaryListener] hands the default listener role back.
+pub enum RrHandBack {
+    /// Only when its entity despawns or the component is removed.
+    OnDespawn,
+
+    /// After this long, or when its entity despawns.
+    After(Duration),
+
+    /// When this playing ID ends, or when its entity despawns.
+    ///
+    /// The event must be posted with [AkCallbackType::AK_EndOfEvent] and a [CallbackChannel].
+    OnEndOfEvent(AkPlayingID),
+}
+
+#[derive(Debug, Clone, Copy, Component)]
+/// Makes the [RrListener] of the same entity the only default listener once registered, then
+/// restores the previous default listeners when [Self::hand_back] says so.
+///
+/// Meant for cutscene cameras: spawn one with an [RrListenerBundle], and gameplay audio comes
+/// back to the player camera on its own when the cutscene ends.
+///
+/// ### Example
+/// ```rust,ignore
+/// let cutscene = rr.post_event("Play_Cutscene_01", AkCallbackType::AK_EndOfEvent, Some(cb_channel.clone()));
+/// commands.entity(cutscene_camera).with_children(|parent| {
+///     parent.spawn((
+///         RrListenerBundle::default(),
+///         RrTemporaryListener::new(RrHandBack::OnEndOfEvent(cutscene)),
+///     ));
+/// });
+/// ```
+pub struct RrTemporaryListener {
+    pub hand_back: RrHandBack,
+}
+
+impl RrTemporaryListener {
+    pub fn new(hand_back: RrHandBack) -> Self {
+        Self { hand_back }
+    }
+}
+
 #[derive(Bundle, Default)]
 /// Sound listener.
 ///
@@ -156,9 +494,16 @@ impl Default for RrEmitter {
             flags: AkCallbackType::default(),
             auto_post: false,
             despawn_on_silent: false,
-            // stop_on_destroy: true, // TODO
-            playing_ids: Arc::new(RwLock::new(vec![])),
+            rtpc_presets: vec![],
+            switch_presets: vec![],
+            variation: None,
+            on_event_change: RrEventChange::Ignore,
+            stop_on_destroy: true,
+            playing_events: Arc::new(RwLock::new(vec![])),
+            end_wakers: default(),
             entity: None,
+            name: None,
+            known_event_id: 0,
         }
     }
 }
@@ -193,19 +538,46 @@ impl RrEmitterBundle {
     }
 
     /// Sets the callback flags to associate with this emitter.
-    pub fn with_flags(mut self, flags: AkCallbackType) -> Self {
-        self.rr.flags = flags;
+    ///
+    /// *See also* [CallbackPreset](crate::CallbackPreset)
+    pub fn with_flags<T: Into<AkCallbackType>>(mut self, flags: T) -> Self {
+        self.rr.flags = flags.into();
+        self
+    }
+
+    /// Sets an initial RTPC value on this emitter, applied as soon as it gets registered.
+    pub fn with_rtpc<T: Into<AkID<'static>>>(mut self, rtpc: T, value: AkRtpcValue) -> Self {
+        self.rr.rtpc_presets.push((rtpc.into(), value));
+        self
+    }
+
+    /// Randomizes pitch and volume RTPCs before each post from this emitter.
+    pub fn with_variation(mut self, variation: RrVariation) -> Self {
+        self.rr.variation = Some(variation);
+        self
+    }
+
+    /// Sets what happens when the event of this emitter gets changed after its registration.
+    pub fn with_event_change(mut self, policy: RrEventChange) -> Self {
+        self.rr.on_event_change = policy;
+        self
+    }
+
+    /// Sets an initial switch value on this emitter, applied as soon as it gets registered.
+    ///
+    /// `group` and `state` should both be names or both be IDs.
+    pub fn with_switch<T: Into<AkID<'static>>>(mut self, group: T, state: T) -> Self {
+        self.rr.switch_presets.push((group.into(), state.into()));
         self
     }
 
-    // TODO
-    // /// Sets whether to automatically stop the sounds emitted by this emitter when it gets destroyed.
-    // ///
-    // /// Defaults to `true`.
-    // pub fn stop_on_destroy(mut self, stop_on_destroy: bool) -> Self {
-    //     self.rr.stop_on_destroy = stop_on_destroy;
-    //     self
-    // }
+    /// Sets whether to automatically stop the sounds emitted by this emitter when it gets destroyed.
+    ///
+    /// Defaults to `true`.
+    pub fn stop_on_destroy(mut self, stop_on_destroy: bool) -> Self {
+        self.rr.stop_on_destroy = stop_on_destroy;
+        self
+    }
 }
 
 impl RrDynamicEmitterBundle {
@@ -243,19 +615,88 @@ impl RrDynamicEmitterBundle {
     }
 
     /// Sets the callback flags to associate with this emitter.
-    pub fn with_flags(mut self, flags: AkCallbackType) -> Self {
-        self.emitter.rr.flags = flags;
+    ///
+    /// *See also* [CallbackPreset](crate::CallbackPreset)
+    pub fn with_flags<T: Into<AkCallbackType>>(mut self, flags: T) -> Self {
+        self.emitter.rr.flags = flags.into();
+        self
+    }
+
+    /// Sets an initial RTPC value on this emitter, applied as soon as it gets registered.
+    pub fn with_rtpc<T: Into<AkID<'static>>>(self, rtpc: T, value: AkRtpcValue) -> Self {
+        Self {
+            emitter: self.emitter.with_rtpc(rtpc, value),
+            ..self
+        }
+    }
+
+    /// Randomizes pitch and volume RTPCs before each post from this emitter.
+    pub fn with_variation(self, variation: RrVariation) -> Self {
+        Self {
+            emitter: self.emitter.with_variation(variation),
+            ..self
+        }
+    }
+
+    /// Sets what happens when the event of this emitter gets changed after its registration.
+    pub fn with_event_change(self, policy: RrEventChange) -> Self {
+        Self {
+            emitter: self.emitter.with_event_change(policy),
+            ..self
+        }
+    }
+
+    /// Sets an initial switch value on this emitter, applied as soon as it gets registered.
+    ///
+    /// `group` and `state` should both be names or both be IDs.
+    pub fn with_switch<T: Into<AkID<'static>>>(self, group: T, state: T) -> Self {
+        Self {
+            emitter: self.emitter.with_switch(group, state),
+            ..self
+        }
+    }
+
+    /// Sets whether to automatically stop the sounds emitted by this emitter when it gets destroyed.
+    ///
+    /// Defaults to `true`.
+    pub fn stop_on_destroy(mut self, stop_on_destroy: bool) -> Self {
+        self.emitter.rr.stop_on_destroy = stop_on_destroy;
+        self
+    }
+}
+
+impl RrEmitter2dBundle {
+    /// Creates an emitter at `position`.
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            emitter: RrDynamicEmitterBundle::new(position.extend(0.)),
+            ..default()
+        }
+    }
+
+    /// Interprets this emitter's position in world space rather than relative to the listener.
+    pub fn in_world_space(mut self) -> Self {
+        self.space = RrEmitterSpace::World;
         self
     }
 
-    // TODO
-    // /// Sets whether to automatically stop the sounds emitted by this emitter when it gets destroyed.
-    // ///
-    // /// Defaults to `true`.
-    // pub fn stop_on_destroy(mut self, stop_on_destroy: bool) -> Self {
-    //     self.emitter.rr.stop_on_destroy = stop_on_destroy;
-    //     self
-    // }
+    /// Sets the event to associate to this emitter and registers it for auto play.
+    pub fn with_event<T: Into<AkID<'static>>>(self, event: T, despawn_on_silent: bool) -> Self {
+        Self {
+            emitter: self.emitter.with_event(event, despawn_on_silent),
+            ..self
+        }
+    }
+
+    /// Sets the callback flags to associate with this emitter.
+    ///
+    /// *See also* [CallbackPreset](crate::CallbackPreset)
+    pub fn with_flags<T: Into<AkCallbackType>>(self, flags: T) -> Self {
+        Self {
+            emitter: self.emitter.with_flags(flags),
+            ..self
+        }
+    }
 }
 
 impl RrListenerBundle {
@@ -271,109 +712,560 @@ impl RrListenerBundle {
         }
     }
 
+    /// Creates a listener at the origin that is not part of the default listeners set.
+    ///
+    /// *See also* [Self::with_is_default()]
+    pub fn non_default() -> Self {
+        default::<Self>().with_is_default(false)
+    }
+
     /// Sets the rotation of this listener.
     pub fn with_rotation(mut self, rotation: Quat) -> Self {
         self.tfm.local = self.tfm.local.with_rotation(rotation);
         // self.tfm.global will get updated by Bevy
         self
     }
-}
 
-#[doc(hidden)]
-macro_rules! post_event_internal {
-    ($event_id:ident on $entity:ident with $flags:expr; store in $safe_playing_ids:ident; react with $cb_info:ident then { $($then:stmt)* }) => {
-        PostEvent::new($entity.index() as AkGameObjectID, $event_id)
-            .flags($flags | AkCallbackType::AK_EndOfEvent)
-            .post_with_callback(move |$cb_info| {
-                {
-                    $($then)*
-                }
+    /// Sets the offset of this listener relative to the entity it will be attached to.
+    pub fn with_parent_offset(mut self, offset: Vec3) -> Self {
+        self.tfm.local.translation = offset;
+        // self.tfm.global will get updated by Bevy
+        self
+    }
 
-                if let AkCallbackInfo::Event {
-                    playing_id,
-                    callback_type: AkCallbackType::AK_EndOfEvent,
-                    ..
-                } = $cb_info
-                {
-                    let mut lock = $safe_playing_ids.write().unwrap();
-                    (*lock).retain(|&p_id| p_id != playing_id);
-                };
-            })
-    };
-    ($event_id:ident on $entity:ident with $flags:expr; store in $safe_playing_ids:ident) => {
-        post_event_internal![$event_id on $entity with $flags; store in $safe_playing_ids; react with cb_info then {}]
-    };
+    /// Sets the name this listener will be registered with in Wwise, followed by its entity ID.
+    ///
+    /// A [`Name`] component on the same entity takes precedence over this.
+    ///
+    /// Ignored with the `strip-names` feature.
+    #[cfg_attr(feature = "strip-names", allow(unused_variables, unused_mut))]
+    pub fn with_name<T: Into<String>>(mut self, name: T) -> Self {
+        #[cfg(not(feature = "strip-names"))]
+        {
+            self.listener.name = Some(name.into());
+        }
+        self
+    }
 }
 
-impl RrEmitter {
-    /// Whether any events are playing on this emitter
-    pub fn is_playing(&self) -> bool {
-        !self.playing_ids.read().unwrap().is_empty()
+#[derive(SystemParam)]
+/// Registered listeners of the world.
+///
+/// Use it in your systems to change which listener is the default one at runtime, for instance
+/// when switching from the player camera to a drone camera.
+pub struct RrListeners<'w, 's> {
+    listeners: Query<'w, 's, (Entity, &'static mut RrListener), With<RrRegistered>>,
+}
+
+impl<'w, 's> RrListeners<'w, 's> {
+    /// Makes `entity` the only default listener.
+    ///
+    /// The new default listener is added before the previous ones are removed, so that emitters
+    /// are never left without a default listener.
+    ///
+    /// Fails with [AkResult::AK_IDNotFound] if `entity` has no registered [RrListener]; listeners
+    /// get registered in the [CoreStage::PreUpdate] stage following their spawn.
+    pub fn set_default(&mut self, entity: Entity) -> Result<(), AkResult> {
+        let Ok((_, mut new_default)) = self.listeners.get_mut(entity) else {
+            error!(
+                "Couldn't make {} the default listener: it is not a registered RrListener",
+                RrObjDesc::new(Some(entity), None)
+            );
+            return Err(AkResult::AK_IDNotFound);
+        };
+
+        if !new_default.is_default {
+            add_default_listener(ak_id_of(entity))?;
+            new_default.is_default = true;
+        }
+
+        for (e, mut rr_l) in self.listeners.iter_mut() {
+            if e != entity && rr_l.is_default {
+                remove_default_listener(ak_id_of(e))?;
+                rr_l.is_default = false;
+            }
+        }
+
+        Ok(())
     }
 
-    /// Whether this component appears to be registered in Wwise.
+    /// Makes `entity` a default listener too, keeping the current ones.
     ///
-    /// You can make sure of this by also querying for the [`RrRegistered`] component on your entities.
-    pub fn is_registered(&self) -> bool {
-        self.entity.is_some()
+    /// Fails with [AkResult::AK_IDNotFound] if `entity` has no registered [RrListener].
+    pub fn add_default(&mut self, entity: Entity) -> Result<(), AkResult> {
+        let Ok((_, mut rr_l)) = self.listeners.get_mut(entity) else {
+            error!(
+                "Couldn't add {} to the default listeners: it is not a registered RrListener",
+                RrObjDesc::new(Some(entity), None)
+            );
+            return Err(AkResult::AK_IDNotFound);
+        };
+
+        if !rr_l.is_default {
+            add_default_listener(ak_id_of(entity))?;
+            rr_l.is_default = true;
+        }
+
+        Ok(())
     }
 
-    /// Stops all events currently playing on this emitter.
-    pub fn stop(&self) {
-        if let Some(entity) = self.entity {
-            stop_all(Some(entity.index() as u64));
+    /// Iterates over the entities of the current default listeners.
+    pub fn defaults(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.listeners
+            .iter()
+            .filter(|(_, rr_l)| rr_l.is_default)
+            .map(|(e, _)| e)
+    }
+}
+
+/// ID of the Wwise game object of `entity`, to target it with raw Rrise calls.
+///
+/// The game object is only known by Wwise once a bevy-rrise component of `entity` got
+/// registered; see [RrRegistered].
+pub fn ak_id_of(entity: Entity) -> AkGameObjectID {
+    entity.index() as AkGameObjectID
+}
+
+#[derive(SystemParam)]
+/// Registered Wwise game objects of the world.
+///
+/// Use it to find back the entity of a game object ID coming from raw Rrise calls or callbacks.
+pub struct RrGameObjects<'w, 's> {
+    entities: &'w Entities,
+    registered: Query<'w, 's, (), With<RrRegistered>>,
+}
+
+impl<'w, 's> RrGameObjects<'w, 's> {
+    /// Entity of the game object `ak_id`, if it is registered by bevy-rrise.
+    pub fn entity_of(&self, ak_id: AkGameObjectID) -> Option<Entity> {
+        let index = u32::try_from(ak_id).ok()?;
+        self.entities
+            .resolve_from_id(index)
+            .filter(|e| self.registered.contains(*e))
+    }
+}
+
+/// Default listeners to restore for each active [RrTemporaryListener], and when it started.
+#[derive(Default)]
+pub(crate) struct TemporaryListeners(HashMap<Entity, (Vec<Entity>, f64)>);
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn update_temporary_listeners(
+    mut commands: Commands,
+    mut listeners: RrListeners,
+    new_temporaries: Query<Entity, (With<RrTemporaryListener>, Added<RrRegistered>)>,
+    temporaries: Query<&RrTemporaryListener>,
+    removed: RemovedComponents<RrTemporaryListener>,
+    mut callbacks: EventReader<AkCallbackEvent>,
+    time: Res<Time>,
+    mut active: Local<TemporaryListeners>,
+) -> Result<(), AkResult> {
+    let now = time.elapsed_seconds_f64();
+
+    for e in new_temporaries.iter() {
+        let previous = listeners.defaults().filter(|&d| d != e).collect();
+        listeners.set_default(e)?;
+        active.0.insert(e, (previous, now));
+        debug!(
+            "Temporary listener {} is now the default listener",
+            e.index()
+        );
+    }
+
+    let ended: Vec<AkPlayingID> = callbacks
+        .iter()
+        .filter(|cb| cb.callback_type() == AkCallbackType::AK_EndOfEvent)
+        .filter_map(|cb| cb.playing_id())
+        .collect();
+
+    let mut hand_backs: Vec<Entity> = removed.iter().collect();
+    for (&e, &(_, started)) in active.0.iter() {
+        let Ok(temporary) = temporaries.get(e) else {
+            continue;
+        };
+        let done = match temporary.hand_back {
+            RrHandBack::OnDespawn => false,
+            RrHandBack::After(duration) => now - started >= duration.as_secs_f64(),
+            RrHandBack::OnEndOfEvent(playing_id) => ended.contains(&playing_id),
+        };
+        if done {
+            commands.entity(e).remove::<RrTemporaryListener>();
+            hand_backs.push(e);
         }
     }
 
-    /// Posts the event `self.event_id` using flags `self.flags`.
-    ///
-    /// If you pass [`None`] for `cb_channel`, you won't receive any [`AkCallbackEvent`](crate::AkCallbackEvent)
-    /// in your [`EventReader`]s, even if you had some flags set in `self.flags`.
-    ///
-    /// See [`CallbackChannel`]
-    pub fn post_associated_event(&mut self, cb_channel: Option<CallbackChannel>) -> AkPlayingID {
-        self.post_event(self.event_id, self.flags, cb_channel)
+    for e in hand_backs {
+        let Some((previous, _)) = active.0.remove(&e) else {
+            continue;
+        };
+
+        let previous: Vec<Entity> = previous
+            .into_iter()
+            .filter(|&p| listeners.listeners.contains(p))
+            .collect();
+        let Some((&first, others)) = previous.split_first() else {
+            warn!(
+                "Temporary listener {} has no previous default listener to hand back to",
+                e.index()
+            );
+            continue;
+        };
+        listeners.set_default(first)?;
+        for &p in others {
+            listeners.add_default(p)?;
+        }
+        debug!(
+            "Temporary listener {} handed back the default listener",
+            e.index()
+        );
     }
 
-    /// Posts `event` using `flags` (**this method ignores `self.flags`**).
-    ///
-    /// If you pass [`None`] for `cb_channel`, you won't receive any [`AkCallbackEvent`](crate::AkCallbackEvent)
-    /// in your [`EventReader`]s, even if you had some `flags`.
-    ///
-    /// See [`CallbackChannel`]
+    Ok(())
+}
+
+/// Spawns a default listener as a child of `camera` and returns its entity.
+///
+/// The listener sits at the camera's origin and follows its orientation.
+pub fn spawn_listener_for_camera(commands: &mut Commands, camera: Entity) -> Entity {
+    let listener = commands
+        .spawn(RrListenerBundle::default().with_name("RrCameraListener"))
+        .id();
+    commands.entity(camera).add_child(listener);
+    listener
+}
+
+/// Shortcuts to spawn emitters and listeners with all their required components.
+///
+/// Mostly useful in tests and examples; use the bundles directly for anything more elaborate.
+pub trait RrCommandsExt {
+    /// Spawns a dynamic emitter at `position` that auto posts `event`, and returns its entity.
+    ///
+    /// The emitter isn't despawned when silent.
+    fn spawn_rr_emitter<T: Into<AkID<'static>>>(&mut self, position: Vec3, event: T) -> Entity;
+
+    /// Spawns a default listener at `position` and returns its entity.
+    fn spawn_rr_listener(&mut self, position: Vec3) -> Entity;
+}
+
+impl<'w, 's> RrCommandsExt for Commands<'w, 's> {
+    fn spawn_rr_emitter<T: Into<AkID<'static>>>(&mut self, position: Vec3, event: T) -> Entity {
+        self.spawn(RrDynamicEmitterBundle::new(position).with_event(event, false))
+            .id()
+    }
+
+    fn spawn_rr_listener(&mut self, position: Vec3) -> Entity {
+        self.spawn(RrListenerBundle::new(position)).id()
+    }
+}
+
+/// Human-readable description of an object known to Rrise, used to make logs actionable.
+///
+/// Displays as `entity 12v3 'DroneBody'`, or `entity 12v3` if the entity has no [`Name`].
+pub(crate) struct RrObjDesc<'a> {
+    entity: Option<Entity>,
+    name: Option<&'a str>,
+}
+
+impl<'a> RrObjDesc<'a> {
+    pub(crate) fn new(entity: Option<Entity>, name: Option<&'a str>) -> Self {
+        Self { entity, name }
+    }
+}
+
+impl<'a> Display for RrObjDesc<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (self.entity, self.name) {
+            (Some(e), Some(name)) => write!(f, "entity {:?} '{}'", e, name),
+            (Some(e), None) => write!(f, "entity {:?}", e),
+            (None, Some(name)) => write!(f, "unregistered object '{}'", name),
+            (None, None) => write!(f, "unregistered object"),
+        }
+    }
+}
+
+/// Hints at the most common cause of a failed post, since Wwise doesn't tell us more.
+pub(crate) fn post_failure_hint(akr: AkResult) -> &'static str {
+    match akr {
+        AkResult::AK_Fail | AkResult::AK_IDNotFound | AkResult::AK_InvalidID => {
+            " (is the bank containing this event loaded?)"
+        }
+        _ => "",
+    }
+}
+
+/// Incremented each time an emitter stops playing its last event, so that silent emitters are
+/// only looked for when some might exist.
+static SILENCED_EMITTERS: AtomicU64 = AtomicU64::new(0);
+
+#[doc(hidden)]
+macro_rules! post_event_internal {
+    ($event_id:ident on $entity:ident with $flags:expr; store in $safe_playing_ids:ident; wake $safe_end_wakers:ident; react with $cb_info:ident then { $($then:stmt)* }) => {
+        {
+            count_post();
+            PostEvent::new(ak_id_of($entity), $event_id)
+                .flags($flags | AkCallbackType::AK_EndOfEvent)
+                .post_with_callback(move |$cb_info| {
+                    {
+                        $($then)*
+                    }
+
+                    match $cb_info {
+                        AkCallbackInfo::Event {
+                            playing_id,
+                            callback_type: AkCallbackType::AK_EndOfEvent,
+                            ..
+                        } => {
+                            let mut lock = $safe_playing_ids.write().unwrap();
+                            (*lock).retain(|playing| playing.playing_id != playing_id);
+                            if (*lock).is_empty() {
+                                SILENCED_EMITTERS.fetch_add(1, Ordering::Relaxed);
+                            }
+                            drop(lock);
+
+                            // Must come after the removal; see RrEventEnd::poll()
+                            if let Some(waker) = $safe_end_wakers.lock().unwrap().remove(&playing_id) {
+                                waker.wake();
+                            }
+                        }
+                        AkCallbackInfo::Duration {
+                            playing_id,
+                            estimated_duration,
+                            ..
+                        } => {
+                            let mut lock = $safe_playing_ids.write().unwrap();
+                            if let Some(playing) = (*lock).iter_mut().find(|playing| playing.playing_id == playing_id) {
+                                playing.add_estimated_duration(estimated_duration);
+                            }
+                        }
+                        _ => {}
+                    };
+                })
+        }
+    };
+    ($event_id:ident on $entity:ident with $flags:expr; store in $safe_playing_ids:ident; wake $safe_end_wakers:ident) => {
+        post_event_internal![$event_id on $entity with $flags; store in $safe_playing_ids; wake $safe_end_wakers; react with cb_info then {}]
+    };
+}
+
+impl RrEmitter {
+    fn desc(&self) -> RrObjDesc<'_> {
+        RrObjDesc::new(self.entity, self.name.as_deref())
+    }
+
+    /// Makes the current event the known one, so that changing it doesn't trigger
+    /// [`on_event_change`](RrEmitter::on_event_change).
+    pub(crate) fn acknowledge_event_change(&mut self) {
+        self.known_event_id = unique_id(self.event_id);
+    }
+
+    /// Whether any events are playing on this emitter
+    pub fn is_playing(&self) -> bool {
+        !self.playing_events.read().unwrap().is_empty()
+    }
+
+    /// Whether the event posted with `playing_id` on this emitter is still playing.
+    pub fn is_playing_id(&self, playing_id: AkPlayingID) -> bool {
+        is_playing_id(&self.playing_events, playing_id)
+    }
+
+    /// Snapshot of the events playing on this emitter.
+    pub fn playing_events(&self) -> Vec<RrPlayingEvent> {
+        self.playing_events.read().unwrap().clone()
+    }
+
+    /// Whether this component appears to be registered in Wwise.
+    ///
+    /// You can make sure of this by also querying for the [`RrRegistered`] component on your entities.
+    pub fn is_registered(&self) -> bool {
+        self.entity.is_some()
+    }
+
+    /// Stops all events currently playing on this emitter.
+    ///
+    /// Same as [Self::stop_all_on_object()].
+    pub fn stop(&self) {
+        self.stop_all_on_object();
+    }
+
+    /// Stops everything playing on the Wwise game object of this emitter, including events
+    /// posted on it with raw rrise calls.
+    ///
+    /// *Remark* Stopping only the events posted through this component, or fading them out, would
+    /// need `AK::SoundEngine::StopPlayingID` or `ExecuteActionOnEvent`, which Rrise doesn't bind yet.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(emitter = %self.desc(), game_obj_id = ?self.entity.map(|e| e.index()))
+    )]
+    pub fn stop_all_on_object(&self) {
+        if let Some(entity) = self.entity {
+            stop_all(Some(entity.index() as u64));
+        }
+    }
+
+    /// Posts `event` and returns a future resolving when it ends, so that scripts running in
+    /// async tasks can wait for a voice line to finish.
+    ///
+    /// The future resolves to an error right away if the event couldn't be posted. It doesn't
+    /// need any Bevy system to make progress.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// let line_done = rr.post_event_async("Play_Cutscene_Line_01");
+    /// AsyncComputeTaskPool::get().spawn(async move {
+    ///     line_done.await.ok();
+    ///     // Next step of the cutscene...
+    /// });
+    /// ```
+    pub fn post_event_async<'b, T: Into<AkID<'b>>>(&mut self, event: T) -> RrEventEnd {
+        RrEventEnd {
+            playing_id: self.post_event(event, AkCallbackType(0), None),
+            playing_events: self.playing_events.clone(),
+            end_wakers: self.end_wakers.clone(),
+        }
+    }
+
+    /// Posts the event `self.event_id` using flags `self.flags`.
+    ///
+    /// If you pass [`None`] for `cb_channel`, you won't receive any [`AkCallbackEvent`](crate::AkCallbackEvent)
+    /// in your [`EventReader`]s, even if you had some flags set in `self.flags`.
+    ///
+    /// See [`CallbackChannel`]
+    pub fn post_associated_event(&mut self, cb_channel: Option<CallbackChannel>) -> AkPlayingID {
+        self.post_event(self.event_id, self.flags, cb_channel)
+    }
+
+    /// Posts `event` using `flags` (**this method ignores `self.flags`**), and calls `closure` for
+    /// each of its callbacks.
+    ///
+    /// Wwise callbacks run on a Wwise thread; `closure` doesn't: it's called on the Bevy side in
+    /// [RriseLabel::RriseCallbackEventsPopulated](crate::plugin::RriseLabel::RriseCallbackEventsPopulated),
+    /// with [Commands] to act on the world. It is dropped after the end of event callback.
+    ///
+    /// `closure` may call any rrise or bevy-rrise API, including posting other events with
+    /// closures; calls needing the world go through `Commands`. Raw rrise callbacks should use
+    /// [defer()](crate::sound_engine::defer()) instead.
+    ///
+    /// Callbacks are still sent as [`AkCallbackEvent`](crate::AkCallbackEvent)s as well.
+    pub fn post_with_closure<'b, T, F>(
+        &mut self,
+        event: T,
+        flags: AkCallbackType,
+        cb_channel: CallbackChannel,
+        closure: F,
+    ) -> AkPlayingID
+    where
+        T: Into<AkID<'b>>,
+        F: FnMut(&mut Commands, &AkCallbackInfo) + Send + Sync + 'static,
+    {
+        let closures = cb_channel.closures.clone();
+
+        // Locked while posting so that the closure is stored before its callbacks get processed
+        let mut closures = closures.lock().unwrap();
+        let playing_id = self.post_event(
+            event,
+            flags | AkCallbackType::AK_EndOfEvent,
+            Some(cb_channel),
+        );
+        if playing_id != AK_INVALID_PLAYING_ID {
+            closures.insert(playing_id, Box::new(closure));
+        }
+        playing_id
+    }
+
+    /// Posts `event` using `flags` (**this method ignores `self.flags`**).
+    ///
+    /// If you pass [`None`] for `cb_channel`, you won't receive any [`AkCallbackEvent`](crate::AkCallbackEvent)
+    /// in your [`EventReader`]s, even if you had some `flags`. If you pass one without `flags`,
+    /// [RriseBasicSettings::default_callback_flags](crate::plugin::RriseBasicSettings::default_callback_flags)
+    /// are used.
+    ///
+    /// Returns [AK_INVALID_PLAYING_ID] without posting while audio is disabled (see
+    /// [RrEngine](crate::plugin::RrEngine)).
+    ///
+    /// See [`CallbackChannel`]
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(
+            emitter = %self.desc(),
+            game_obj_id = ?self.entity.map(|e| e.index()),
+            event = tracing::field::Empty,
+        )
+    )]
     pub fn post_event<'b, T: Into<AkID<'b>>>(
         &mut self,
         event: T,
         flags: AkCallbackType,
         cb_channel: Option<CallbackChannel>,
     ) -> AkPlayingID {
+        let event = event.into();
+        tracing::Span::current().record("event", tracing::field::display(event));
+
+        if !crate::plugin::is_enabled() {
+            debug!("Audio disabled; '{}' not posted on {}", event, self.desc());
+            return AK_INVALID_PLAYING_ID;
+        }
+        record_post(event);
+
+        let flags = if flags.0 == 0 && cb_channel.is_some() {
+            crate::plugin::default_callback_flags()
+        } else {
+            flags
+        };
+
         if let Some(entity) = self.entity {
+            if let Some(variation) = &self.variation {
+                if let Err(akr) = variation.apply(ak_id_of(entity)) {
+                    error!("Couldn't apply variation on {} - {}", self.desc(), akr);
+                }
+            }
+
             let has_flags = flags.0 > AkCallbackType(0).0;
-            let event = event.into();
-            let safe_playing_ids = self.playing_ids.clone();
+            let internal_flags = if crate::plugin::tracks_durations() {
+                AkCallbackType::AK_Duration
+            } else {
+                AkCallbackType(0)
+            };
+            let requested_flags = flags | internal_flags;
+            let safe_playing_ids = self.playing_events.clone();
+            let safe_end_wakers = self.end_wakers.clone();
+
+            // Hold the lock while posting: the EndOfEvent callback could otherwise run before the
+            // playing ID gets stored, and the ID would then never be removed
+            let mut playing_events = self.playing_events.write().unwrap();
+            // Only posts forwarding their callbacks, EndOfEvent included, can be followed
+            let subscribers = if has_flags { cb_channel.clone() } else { None };
             let post_result = match (has_flags, cb_channel) {
                 (false, _) => {
                     post_event_internal![
-                        event on entity with flags;
-                        store in safe_playing_ids]
+                        event on entity with requested_flags;
+                        store in safe_playing_ids;
+                        wake safe_end_wakers]
                 }
                 (true, None) => {
                     warn!(
-                        "Event {} on {:?} wants callbacks {} but didn't pass a CallbackChannel; you won't receive bevy events for it",
+                        "Event '{}' on {} wants callbacks {} but didn't pass a CallbackChannel; you won't receive bevy events for it",
                         event,
-                        self.entity,
+                        self.desc(),
                         flags,
                     );
                     post_event_internal![
-                        event on entity with AkCallbackType(0);
-                        store in safe_playing_ids]
+                        event on entity with internal_flags;
+                        store in safe_playing_ids;
+                        wake safe_end_wakers]
                 }
                 (true, Some(cb_channel)) => {
                     post_event_internal![
-                    event on entity with flags;
+                    event on entity with requested_flags;
                     store in safe_playing_ids;
+                    wake safe_end_wakers;
                     react with cb_info then {
-                        if cb_channel.sender.try_send(cb_info.clone()).is_err() {
+                        // Durations may only be tracked internally
+                        if (!matches!(cb_info, AkCallbackInfo::Duration { .. })
+                            || flags.contains(AkCallbackType::AK_Duration))
+                            && cb_channel.send(cb_info.clone()).is_err()
+                        {
                             warn!("Could not send {:?}", cb_info);
                         }
                     }]
@@ -382,101 +1274,379 @@ impl RrEmitter {
 
             match post_result {
                 Ok(playing_id) => {
-                    self.playing_ids.write().unwrap().push(playing_id);
+                    playing_events.push(RrPlayingEvent::new(playing_id, event));
+                    if let Some(cb_channel) = subscribers {
+                        cb_channel.track(playing_id, event);
+                    }
                     playing_id
                 }
                 Err(akr) => {
-                    error!("Couldn't post '{}' on {:?} - {}", event, self.entity, akr);
+                    error!(
+                        "Couldn't post '{}' on {} - {}{}",
+                        event,
+                        self.desc(),
+                        akr,
+                        post_failure_hint(akr)
+                    );
                     AK_INVALID_PLAYING_ID
                 }
             }
         } else {
-            error!("RrComponent is not yet registered: {:?}", self);
+            error!(
+                "Couldn't post '{}' on {}: RrEmitter is not yet registered",
+                event,
+                self.desc()
+            );
             AK_INVALID_PLAYING_ID
         }
     }
 }
 
+#[derive(Debug, Clone)]
+/// Event playing on an [RrEmitter].
+///
+/// *See also* [RrEmitter::playing_events()]
+pub struct RrPlayingEvent {
+    pub(crate) playing_id: AkPlayingID,
+    event_id: AkUniqueID,
+    #[cfg(debug_assertions)]
+    pub(crate) event_name: Option<String>,
+    started: Instant,
+    estimated_duration: Option<Duration>,
+}
+
+impl RrPlayingEvent {
+    fn new(playing_id: AkPlayingID, event: AkID) -> Self {
+        Self {
+            playing_id,
+            event_id: unique_id(event),
+            #[cfg(debug_assertions)]
+            event_name: match event {
+                AkID::Name(name) => Some(name.to_string()),
+                AkID::ID(_) => None,
+            },
+            started: Instant::now(),
+            estimated_duration: None,
+        }
+    }
+
+    pub fn playing_id(&self) -> AkPlayingID {
+        self.playing_id
+    }
+
+    pub fn event_id(&self) -> AkUniqueID {
+        self.event_id
+    }
+
+    /// Time since the event was posted.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Estimated duration of the longest sound the event played so far, accounting for pitch.
+    ///
+    /// `None` until Wwise reports it, or if durations aren't tracked (see
+    /// [RriseBasicSettings::track_durations](crate::plugin::RriseBasicSettings::track_durations)).
+    pub fn estimated_duration(&self) -> Option<Duration> {
+        self.estimated_duration
+    }
+
+    /// Progress of the event from `0` to `1`, if its duration is known; handy for VO progress bars.
+    pub fn progress(&self) -> Option<f32> {
+        self.estimated_duration
+            .filter(|duration| !duration.is_zero())
+            .map(|duration| (self.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.))
+    }
+
+    fn add_estimated_duration(&mut self, millis: f32) {
+        let duration = Duration::from_secs_f32(millis.max(0.) / 1000.);
+        if !matches!(self.estimated_duration, Some(known) if known >= duration) {
+            self.estimated_duration = Some(duration);
+        }
+    }
+}
+
+fn is_playing_id(playing_events: &RwLock<Vec<RrPlayingEvent>>, playing_id: AkPlayingID) -> bool {
+    playing_events
+        .read()
+        .unwrap()
+        .iter()
+        .any(|playing| playing.playing_id == playing_id)
+}
+
+/// Future resolving when an event posted with [RrEmitter::post_event_async()] ends.
+///
+/// Resolves to `Err(AK_Fail)` if the event couldn't be posted. Events still playing when the
+/// sound engine restarts are considered ended.
+pub struct RrEventEnd {
+    playing_id: AkPlayingID,
+    playing_events: Arc<RwLock<Vec<RrPlayingEvent>>>,
+    end_wakers: Arc<Mutex<HashMap<AkPlayingID, Waker>>>,
+}
+
+impl RrEventEnd {
+    pub fn playing_id(&self) -> AkPlayingID {
+        self.playing_id
+    }
+}
+
+impl Future for RrEventEnd {
+    type Output = Result<(), AkResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.playing_id == AK_INVALID_PLAYING_ID {
+            return Poll::Ready(Err(AkResult::AK_Fail));
+        }
+
+        // Locking the wakers first guarantees that if the playing ID is still there, the
+        // EndOfEvent callback will find our waker
+        let mut end_wakers = self.end_wakers.lock().unwrap();
+        if !is_playing_id(&self.playing_events, self.playing_id) {
+            return Poll::Ready(Ok(()));
+        }
+
+        end_wakers.insert(self.playing_id, cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Name of the Wwise game object of `entity`, identifying it in the Wwise profiler.
+#[cfg(not(any(wwrelease, feature = "strip-names")))]
+fn wwise_object_name(kind: &str, entity: Entity, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("{} ({:?})", name, entity),
+        None => format!("{} ({:?})", kind, entity),
+    }
+}
+
+/// Registers a listener whose entity is known in Wwise and sets it up; logs any failure.
+///
+/// Returns whether the listener is now fully registered.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(
+        listener = %RrObjDesc::new(rr_l.entity, rr_l.name.as_deref()),
+        game_obj_id = ?rr_l.entity.map(|e| e.index()),
+    )
+)]
+fn register_listener(rr_l: &RrListener, tfm: &GlobalTransform) -> bool {
+    let e = rr_l.entity.unwrap();
+    let id = ak_id_of(e);
+    let desc = RrObjDesc::new(Some(e), rr_l.name.as_deref());
+
+    #[cfg(not(any(wwrelease, feature = "strip-names")))]
+    if let Err(akr) = register_named_game_obj(
+        id,
+        wwise_object_name("RrListener", e, rr_l.name.as_deref()).as_str(),
+    ) {
+        error!("Couldn't register listener {} - {}", desc, akr);
+        return false;
+    }
+
+    #[cfg(any(wwrelease, feature = "strip-names"))]
+    if let Err(akr) = register_game_obj(id) {
+        error!("Couldn't register listener {} - {}", desc, akr);
+        return false;
+    }
+
+    if rr_l.is_default {
+        if let Err(akr) = add_default_listener(id) {
+            error!("Couldn't add default listener {} - {}", desc, akr);
+            return false;
+        }
+    }
+
+    if let Err(akr) = set_position(id, tfm.to_ak_transform()) {
+        error!("Couldn't set listener {} position - {}", desc, akr);
+        return false;
+    }
+
+    true
+}
+
+/// Registers an emitter whose entity is known in Wwise and applies its presets; logs any failure.
+///
+/// If `shared` is set, the game object of the entity is already registered by its [RrListener].
+///
+/// Returns whether the emitter is now fully registered.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(emitter = %rr_e.desc(), game_obj_id = ?rr_e.entity.map(|e| e.index()))
+)]
+fn register_emitter(rr_e: &RrEmitter, tfm: &GlobalTransform, shared: bool) -> bool {
+    let e = rr_e.entity.unwrap();
+    let id = ak_id_of(e);
+    let desc = rr_e.desc();
+
+    #[cfg(not(any(wwrelease, feature = "strip-names")))]
+    if !shared {
+        if let Err(akr) = register_named_game_obj(
+            id,
+            wwise_object_name("RrEmitter", e, rr_e.name.as_deref()).as_str(),
+        ) {
+            error!("Couldn't register emitter {} - {}", desc, akr);
+            return false;
+        }
+    }
+
+    #[cfg(any(wwrelease, feature = "strip-names"))]
+    if !shared {
+        if let Err(akr) = register_game_obj(id) {
+            error!("Couldn't register emitter {} - {}", desc, akr);
+            return false;
+        }
+    }
+
+    if let Err(akr) = set_position(id, tfm.to_ak_transform()) {
+        error!("Couldn't set emitter {} position - {}", desc, akr);
+        return false;
+    }
+
+    // Presets must be in place before auto posting, otherwise the first frame would play
+    // with the Wwise default values
+    for &(rtpc, value) in rr_e.rtpc_presets.iter() {
+        count_set_rtpc();
+        if let Err(akr) = SetRtpcValue::new(rtpc, value).for_target(id).set() {
+            error!("Couldn't set RTPC '{}' on emitter {} - {}", rtpc, desc, akr);
+        }
+    }
+    for &(group, state) in rr_e.switch_presets.iter() {
+        if let Err(akr) = set_switch(group, state, id) {
+            error!(
+                "Couldn't set switch '{}' to '{}' on emitter {} - {}",
+                group, state, desc, akr
+            );
+        }
+    }
+
+    true
+}
+
+// Replaced emitters get registered on the next frame, once the commands got applied
+#[tracing::instrument(level = "debug", skip_all)]
+#[allow(clippy::type_complexity)]
+pub(crate) fn spawn_scene_rr_objects(
+    mut commands: Commands,
+    emitters: Query<(Entity, &RrSceneEmitter, Option<&GlobalTransform>), Added<RrSceneEmitter>>,
+    listeners: Query<(Entity, &RrListener), (Added<RrListener>, Without<GlobalTransform>)>,
+) {
+    for (e, scene_emitter, tfm) in emitters.iter() {
+        let mut entity_cmds = commands.entity(e);
+        entity_cmds
+            .remove::<RrSceneEmitter>()
+            .insert(scene_emitter.to_emitter());
+
+        // Scenes only hold what was saved; registration needs a global transform
+        if tfm.is_none() {
+            entity_cmds.insert(GlobalTransform::default());
+        }
+
+        debug!(
+            "Scene emitter {} replaced by an RrEmitter",
+            RrObjDesc::new(Some(e), None)
+        );
+    }
+
+    // Inserted again so that it's still new once it can be registered
+    for (e, rr_l) in listeners.iter() {
+        commands
+            .entity(e)
+            .insert((GlobalTransform::default(), RrListener::new(rr_l.is_default)));
+    }
+}
+
 #[tracing::instrument(level = "debug", skip_all)]
+#[cfg_attr(feature = "strip-names", allow(unused_variables))]
+#[allow(clippy::type_complexity)]
 pub(crate) fn init_new_rr_objects(
     mut commands: Commands,
-    mut listeners: Query<
-        (Entity, Option<&Name>, &mut RrListener, &GlobalTransform),
-        Added<RrListener>,
-    >,
+    mut listeners: Query<(
+        Entity,
+        Option<&Name>,
+        &mut RrListener,
+        &GlobalTransform,
+        Option<&RrRegistered>,
+        ChangeTrackers<RrListener>,
+    )>,
     mut emitters: Query<
-        (Entity, Option<&Name>, &mut RrEmitter, &GlobalTransform),
-        Added<RrEmitter>,
+        (
+            Entity,
+            Option<&Name>,
+            &mut RrEmitter,
+            &GlobalTransform,
+            Option<&RrEmitterSpace>,
+            Option<&RrRegistered>,
+            Option<&RrSwitch>,
+            Option<&RrDistanceCulling>,
+            ChangeTrackers<RrEmitter>,
+        ),
+        Or<(Added<RrEmitter>, Changed<RrDistanceCulling>)>,
     >,
     cb_channel: Res<CallbackChannel>,
 ) -> Result<(), AkResult> {
+    let mut registered_listeners = HashSet::new();
+    let mut default_listener_tfm = None;
+
     // Always register listeners first
     // Otherwise, if the first listener was created in the same frame than an emitter with auto-post,
     // this emitter would have no listener and fail to post on the Wwise side.
-    for (e, name, mut rr_l, &tfm) in listeners.iter_mut() {
-        rr_l.entity = Some(e);
-        let id = e.index() as AkGameObjectID;
-
-        #[cfg(not(wwrelease))]
-        {
-            if let Err(akr) = register_named_game_obj(
-                id,
-                name.map(|n| n.as_str())
-                    .unwrap_or(format!("RrListener_{}", e.index()).as_str()),
-            ) {
-                error!("Couldn't register listener {} - {}", id, akr);
-                continue;
+    for (e, name, mut rr_l, &tfm, registered, rr_l_tracker) in listeners.iter_mut() {
+        if !rr_l_tracker.is_added() {
+            if registered.is_some() && rr_l.is_default && default_listener_tfm.is_none() {
+                default_listener_tfm = Some(tfm);
             }
-        }
-
-        #[cfg(wwrelease)]
-        if let Err(akr) = register_game_obj(id) {
-            error!("Couldn't register listener {:?} - {}", e, akr);
             continue;
         }
 
-        if rr_l.is_default {
-            if let Err(akr) = add_default_listener(id) {
-                error!("Couldn't add default listener {:?} - {}", e, akr);
-                continue;
-            }
+        rr_l.entity = Some(e);
+        #[cfg(not(feature = "strip-names"))]
+        if let Some(name) = name {
+            rr_l.name = Some(name.to_string());
         }
 
-        if let Err(akr) = set_position(id, tfm.to_ak_transform()) {
-            error!("Couldn't set listener {:?} position - {}", e, akr);
+        if !register_listener(&rr_l, &tfm) {
             continue;
         }
 
+        registered_listeners.insert(e);
         commands.entity(e).insert(RrRegistered);
+        if rr_l.is_default && default_listener_tfm.is_none() {
+            default_listener_tfm = Some(tfm);
+        }
 
-        debug!("Listener {} now registered", id);
+        debug!("Listener {} now registered", e.index());
     }
 
-    for (e, name, mut rr_e, &tfm) in emitters.iter_mut() {
-        rr_e.entity = Some(e);
-        let id = e.index() as AkGameObjectID;
+    for (e, name, mut rr_e, &tfm, space, registered, switch, culling, rr_e_tracker) in
+        emitters.iter_mut()
+    {
+        // Registered once in range of a listener
+        if matches!(culling, Some(culling) if culling.is_culled()) {
+            continue;
+        }
+        // Emitters changing their culling only need registering when brought back in range
+        if !rr_e_tracker.is_added() && registered.is_some() {
+            continue;
+        }
 
-        #[cfg(not(wwrelease))]
+        rr_e.entity = Some(e);
+        #[cfg(not(feature = "strip-names"))]
         {
-            if let Err(akr) = register_named_game_obj(
-                id,
-                name.map(|n| n.as_str())
-                    .unwrap_or(format!("RrEmitter_{}", e.index()).as_str()),
-            ) {
-                error!("Couldn't register emitter {} - {}", id, akr);
-                continue;
-            }
+            rr_e.name = name.map(|n| n.to_string());
         }
 
-        #[cfg(wwrelease)]
-        if let Err(akr) = register_game_obj(id) {
-            error!("Couldn't register emitter {:?} - {}", e, akr);
+        rr_e.known_event_id = unique_id(rr_e.event_id);
+
+        let shared = registered.is_some() || registered_listeners.contains(&e);
+        let tfm = wwise_transform(tfm, space, default_listener_tfm);
+        if !register_emitter(&rr_e, &tfm, shared) {
             continue;
         }
 
-        if let Err(akr) = set_position(id, tfm.to_ak_transform()) {
-            error!("Couldn't set emitter {:?} position - {}", e, akr);
-            continue;
+        if let Some(switch) = switch {
+            switch.apply(e);
         }
 
         if rr_e.auto_post {
@@ -485,31 +1655,159 @@ pub(crate) fn init_new_rr_objects(
 
         commands.entity(e).insert(RrRegistered);
 
-        debug!("Emitter {} now registered", id);
+        debug!("Emitter {} now registered", e.index());
     }
 
     Ok(())
 }
 
 #[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn apply_event_changes(
+    mut emitters: Query<&mut RrEmitter, (Changed<RrEmitter>, With<RrRegistered>)>,
+    cb_channel: Res<CallbackChannel>,
+) {
+    for mut rr_e in emitters.iter_mut() {
+        let event_id = unique_id(rr_e.event_id);
+        if event_id == rr_e.known_event_id {
+            continue;
+        }
+        rr_e.bypass_change_detection().known_event_id = event_id;
+
+        debug!(
+            "Event of {} changed to '{}' ({:?})",
+            rr_e.desc(),
+            rr_e.event_id,
+            rr_e.on_event_change
+        );
+        match rr_e.on_event_change {
+            RrEventChange::Ignore => {}
+            RrEventChange::StopAndPost => {
+                rr_e.stop();
+                rr_e.post_associated_event(Some(cb_channel.clone()));
+            }
+            RrEventChange::PostAdditional => {
+                rr_e.post_associated_event(Some(cb_channel.clone()));
+            }
+        }
+    }
+}
+
+/// Transform to forward to Wwise for an emitter at `tfm`: [RrEmitterSpace::ListenerRelative]
+/// emitters are placed relative to the default listener at `listener_tfm`, if there is one.
+fn wwise_transform(
+    tfm: GlobalTransform,
+    space: Option<&RrEmitterSpace>,
+    listener_tfm: Option<GlobalTransform>,
+) -> GlobalTransform {
+    match (space, listener_tfm) {
+        (Some(RrEmitterSpace::ListenerRelative), Some(listener_tfm)) => listener_tfm * tfm,
+        _ => tfm,
+    }
+}
+
+pub(crate) fn unique_id(id: AkID) -> AkUniqueID {
+    match id {
+        AkID::ID(id) => id,
+        AkID::Name(name) => get_id_from_string(name),
+    }
+}
+
+/// Entities of the registered emitters, with their [`stop_on_destroy`](RrEmitter::stop_on_destroy),
+/// as of the last run of [stop_destroyed_emitters()].
+///
+/// Diffing against it, rather than relying on [RemovedComponents] which only covers the current
+/// frame, makes sure no stop or unregistration is ever missed whatever the schedule.
+#[derive(Default)]
+pub(crate) struct KnownEmitters(HashMap<Entity, bool>);
+
+#[tracing::instrument(level = "debug", skip_all)]
+#[allow(clippy::type_complexity)]
 pub(crate) fn stop_destroyed_emitters(
-    destroyed_emitters: RemovedComponents<RrEmitter>,
+    emitters: Query<Entity, (With<RrEmitter>, With<RrRegistered>)>,
+    changed_emitters: Query<
+        (Entity, &RrEmitter),
+        (
+            With<RrRegistered>,
+            Or<(Changed<RrEmitter>, Added<RrRegistered>)>,
+        ),
+    >,
+    remaining: Query<(Option<&RrDistanceCulling>, Option<&RrListener>)>,
+    mut known: Local<KnownEmitters>,
 ) -> Result<(), AkResult> {
-    for e in destroyed_emitters.iter() {
-        stop_all(Some(e.index() as AkGameObjectID));
-        debug!("Stopped emitter {} because it got despawned", e.index());
+    known.0.extend(
+        changed_emitters
+            .iter()
+            .map(|(e, rr_e)| (e, rr_e.stop_on_destroy)),
+    );
+
+    // Emitters only leave the set by being removed: same count, nothing to stop
+    if known.0.len() == emitters.iter().len() {
+        return Ok(());
     }
 
+    let current: HashSet<Entity> = emitters.iter().collect();
+    let current_ids: HashSet<AkGameObjectID> = current.iter().map(|&e| ak_id_of(e)).collect();
+    known.0.retain(|&e, &mut stop_on_destroy| {
+        if current.contains(&e) {
+            return true;
+        }
+
+        let (culling, listener) = remaining.get(e).unwrap_or_default();
+        let id = ak_id_of(e);
+        // Culling already stopped and unregistered it; a new emitter may also reuse the index
+        if matches!(culling, Some(culling) if culling.is_culled()) || current_ids.contains(&id) {
+            return false;
+        }
+
+        if stop_on_destroy {
+            stop_all(Some(id));
+            debug!("Stopped emitter {} because it got despawned", e.index());
+        }
+
+        // The game object is the listener's too
+        if listener.is_none() {
+            match unregister_game_obj(id) {
+                Ok(_) => debug!("Unregistered emitter {}", e.index()),
+                Err(akr) => error!("Couldn't unregister emitter {} - {}", e.index(), akr),
+            }
+        }
+        false
+    });
+
     Ok(())
 }
 
 #[tracing::instrument(level = "debug", skip_all)]
+#[allow(clippy::type_complexity)]
 pub(crate) fn despawn_silent_emitters(
     mut commands: Commands,
     emitters: Query<&RrEmitter, With<RrRegistered>>,
+    changed_emitters: Query<
+        &RrEmitter,
+        (
+            With<RrRegistered>,
+            Or<(Changed<RrEmitter>, Added<RrRegistered>)>,
+        ),
+    >,
+    mut last_silenced: Local<u64>,
+    mut last_generation: Local<u32>,
 ) -> Result<(), AkResult> {
-    for rr in emitters.iter() {
-        if rr.despawn_on_silent && rr.playing_ids.read().unwrap().is_empty() {
+    // Emitters only become silent when their last event ends, when a post on them fails (which
+    // requires a mutable access) or when the sound engine restarts
+    let silenced = SILENCED_EMITTERS.load(Ordering::Relaxed);
+    let generation = crate::plugin::sound_engine_generation();
+    let check_all = silenced != *last_silenced || generation != *last_generation;
+    *last_silenced = silenced;
+    *last_generation = generation;
+
+    let candidates: Box<dyn Iterator<Item = &RrEmitter>> = if check_all {
+        Box::new(emitters.iter())
+    } else {
+        Box::new(changed_emitters.iter())
+    };
+
+    for rr in candidates {
+        if rr.despawn_on_silent && !rr.is_playing() {
             commands.entity(rr.entity.unwrap()).despawn();
             debug!(
                 "Despawned emitter {} because it became silent",
@@ -521,10 +1819,80 @@ pub(crate) fn despawn_silent_emitters(
     Ok(())
 }
 
+pub(crate) fn keep_ambiences_playing(
+    mut ambiences: Query<(&mut RrEmitter, &mut RrLoopingAmbience), With<RrRegistered>>,
+    cb_channel: Res<CallbackChannel>,
+    time: Res<Time>,
+) {
+    for (mut rr, mut ambience) in ambiences.iter_mut() {
+        // Culled this frame
+        if !rr.is_registered() {
+            continue;
+        }
+
+        if ambience.playing_id != AK_INVALID_PLAYING_ID && rr.is_playing_id(ambience.playing_id) {
+            continue;
+        }
+
+        let now = time.elapsed_seconds_f64();
+        if now < ambience.next_retry {
+            continue;
+        }
+
+        if ambience.playing_id != AK_INVALID_PLAYING_ID {
+            debug!(
+                "Ambience '{}' on {} died; posting it again",
+                ambience.event,
+                rr.desc()
+            );
+        }
+
+        let flags = rr.flags;
+        ambience.playing_id = rr.post_event(ambience.event, flags, Some(cb_channel.clone()));
+        if ambience.playing_id == AK_INVALID_PLAYING_ID {
+            ambience.next_retry = now + RrLoopingAmbience::RETRY_DELAY;
+        }
+    }
+}
+
+pub(crate) fn despawn_on_callbacks(
+    mut commands: Commands,
+    despawners: Query<(Entity, &RrDespawnOn)>,
+    mut callbacks: EventReader<AkCallbackEvent>,
+) {
+    if despawners.is_empty() {
+        callbacks.clear();
+        return;
+    }
+
+    for cb in callbacks.iter() {
+        let (game_obj_id, callback_type) = (cb.game_obj_id(), cb.callback_type());
+        for (e, despawn_on) in despawners.iter() {
+            let watched = ak_id_of(despawn_on.emitter.unwrap_or(e));
+            if watched == game_obj_id
+                && despawn_on.callback_type.contains(callback_type)
+                && (despawn_on.label.is_none() || despawn_on.label.as_deref() == cb.label())
+            {
+                commands.entity(e).despawn_recursive();
+                debug!(
+                    "Despawned {} on callback {}",
+                    RrObjDesc::new(Some(e), None),
+                    callback_type
+                );
+            }
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_rr_position(
     mut emitters: Query<
-        (&mut RrEmitter, &GlobalTransform),
+        (
+            &mut RrEmitter,
+            &GlobalTransform,
+            Option<&RrEmitterSpace>,
+            Option<&RrCosmetic>,
+        ),
         (With<RrRegistered>, Changed<GlobalTransform>),
     >,
     mut listeners: Query<
@@ -532,18 +1900,279 @@ pub(crate) fn update_rr_position(
         (With<RrRegistered>, Changed<GlobalTransform>),
     >,
 ) -> Result<(), AkResult> {
-    for (rr, &tfm) in emitters.iter_mut() {
-        set_position(
-            rr.entity.unwrap().index() as AkGameObjectID,
-            tfm.to_ak_transform(),
-        )?;
+    if !crate::plugin::is_enabled() {
+        return Ok(());
+    }
+
+    for (rr, &tfm, space, cosmetic) in emitters.iter_mut() {
+        if let Some(RrEmitterSpace::ListenerRelative) = space {
+            // See update_listener_relative_positions()
+            continue;
+        }
+
+        if matches!(cosmetic, Some(c) if !c.is_active()) {
+            continue;
+        }
+
+        if let Err(akr) = set_position(ak_id_of(rr.entity.unwrap()), tfm.to_ak_transform()) {
+            error!("Couldn't update emitter {} position - {}", rr.desc(), akr);
+        }
     }
     for (rr, &tfm) in listeners.iter_mut() {
-        set_position(
-            rr.entity.unwrap().index() as AkGameObjectID,
-            tfm.to_ak_transform(),
-        )?;
+        if let Err(akr) = set_position(ak_id_of(rr.entity.unwrap()), tfm.to_ak_transform()) {
+            error!(
+                "Couldn't update listener {} position - {}",
+                RrObjDesc::new(rr.entity, rr.name.as_deref()),
+                akr
+            );
+        }
     }
 
     Ok(())
 }
+
+/// Keeps the names of Wwise game objects in sync with the [Name] of their entities.
+#[cfg(not(any(wwrelease, feature = "strip-names")))]
+#[allow(clippy::type_complexity)]
+pub(crate) fn rename_rr_objects(
+    mut listeners: Query<(&mut RrListener, Option<&Name>), With<RrRegistered>>,
+    mut emitters: Query<(&mut RrEmitter, Option<&Name>), With<RrRegistered>>,
+    renamed: Query<Entity, (Changed<Name>, Or<(With<RrListener>, With<RrEmitter>)>)>,
+    unnamed: RemovedComponents<Name>,
+) {
+    for e in renamed.iter().chain(unnamed.iter()) {
+        let id = ak_id_of(e);
+
+        // Keep the emitter name in sync even when the game object is named after the listener
+        if let Ok((mut rr_e, name)) = emitters.get_mut(e) {
+            rr_e.name = name.map(|n| n.to_string());
+        }
+
+        if let Ok((mut rr_l, name)) = listeners.get_mut(e) {
+            rr_l.name = name.map(|n| n.to_string());
+            let wwise_name = wwise_object_name("RrListener", e, rr_l.name.as_deref());
+            match register_named_game_obj(id, wwise_name.as_str()) {
+                Ok(_) => debug!("Listener {} renamed '{}'", e.index(), wwise_name),
+                Err(akr) => error!("Couldn't rename listener {} - {}", e.index(), akr),
+            }
+        } else if let Ok((rr_e, _)) = emitters.get(e) {
+            let wwise_name = wwise_object_name("RrEmitter", e, rr_e.name.as_deref());
+            match register_named_game_obj(id, wwise_name.as_str()) {
+                Ok(_) => debug!("Emitter {} renamed '{}'", e.index(), wwise_name),
+                Err(akr) => error!("Couldn't rename emitter {} - {}", e.index(), akr),
+            }
+        }
+    }
+}
+
+/// Flags the transforms of all registered objects as changed when audio gets re-enabled, so that
+/// their positions get synced again.
+pub(crate) fn resume_rr_positions(
+    mut transforms: Query<&mut GlobalTransform, With<RrRegistered>>,
+    mut was_enabled: Local<Option<bool>>,
+) {
+    let enabled = crate::plugin::is_enabled();
+    let resumed = matches!(*was_enabled, Some(false)) && enabled;
+    *was_enabled = Some(enabled);
+    if !resumed {
+        return;
+    }
+
+    for mut tfm in transforms.iter_mut() {
+        tfm.set_changed();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_listener_relative_positions(
+    emitters: Query<
+        (
+            &RrEmitter,
+            &GlobalTransform,
+            &RrEmitterSpace,
+            ChangeTrackers<GlobalTransform>,
+        ),
+        With<RrRegistered>,
+    >,
+    listeners: Query<
+        (
+            &RrListener,
+            &GlobalTransform,
+            ChangeTrackers<GlobalTransform>,
+        ),
+        With<RrRegistered>,
+    >,
+) -> Result<(), AkResult> {
+    if !crate::plugin::is_enabled() {
+        return Ok(());
+    }
+
+    let Some((_, &listener_tfm, listener_changes)) =
+        listeners.iter().find(|(rr_l, _, _)| rr_l.is_default)
+    else {
+        return Ok(());
+    };
+
+    for (rr, &tfm, space, changes) in emitters.iter() {
+        if *space != RrEmitterSpace::ListenerRelative
+            || !(changes.is_changed() || listener_changes.is_changed())
+        {
+            continue;
+        }
+
+        let tfm = wwise_transform(tfm, Some(space), Some(listener_tfm));
+        if let Err(akr) = set_position(ak_id_of(rr.entity.unwrap()), tfm.to_ak_transform()) {
+            error!("Couldn't update emitter {} position - {}", rr.desc(), akr);
+        }
+    }
+
+    Ok(())
+}
+
+/// Registers again all the objects of this world when the sound engine got restarted.
+///
+/// Voices of the previous engine instance are gone: emitters forget about their playing IDs.
+#[allow(clippy::type_complexity)]
+pub(crate) fn reregister_rr_objects(
+    listeners: Query<(&RrListener, &GlobalTransform), With<RrRegistered>>,
+    mut emitters: Query<
+        (
+            &mut RrEmitter,
+            &GlobalTransform,
+            Option<&RrEmitterSpace>,
+            Option<&RrListener>,
+        ),
+        With<RrRegistered>,
+    >,
+    cb_channel: Res<CallbackChannel>,
+    mut last_generation: Local<Option<u32>>,
+) {
+    let generation = crate::plugin::sound_engine_generation();
+    let restarted = matches!(*last_generation, Some(g) if g != generation);
+    *last_generation = Some(generation);
+    if !restarted {
+        return;
+    }
+
+    for (rr_l, tfm) in listeners.iter() {
+        register_listener(rr_l, tfm);
+    }
+    let default_listener_tfm = listeners
+        .iter()
+        .find(|(rr_l, _)| rr_l.is_default)
+        .map(|(_, &tfm)| tfm);
+
+    for (mut rr_e, &tfm, space, rr_l) in emitters.iter_mut() {
+        // Culled this frame
+        if !rr_e.is_registered() {
+            continue;
+        }
+
+        let was_playing = !rr_e.playing_events.read().unwrap().is_empty();
+        rr_e.playing_events.write().unwrap().clear();
+        for (_, waker) in rr_e.end_wakers.lock().unwrap().drain() {
+            waker.wake();
+        }
+
+        // Bring back what was auto posted, typically after soundbanks got hot-reloaded
+        let tfm = wwise_transform(tfm, space, default_listener_tfm);
+        if register_emitter(&rr_e, &tfm, rr_l.is_some()) && rr_e.auto_post && was_playing {
+            rr_e.post_associated_event(Some(cb_channel.clone()));
+        }
+    }
+
+    // Events posted before the restart will never send their EndOfEvent
+    cb_channel.closures.lock().unwrap().clear();
+    cb_channel.forget_tracked_playing_ids();
+
+    debug!("Registered objects again after sound engine restart");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::scene::serde::SceneDeserializer;
+    use bevy::scene::DynamicScene;
+    use serde::de::DeserializeSeed;
+
+    fn type_registry() -> AppTypeRegistry {
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<bool>();
+            registry.register::<String>();
+            registry.register::<Vec<String>>();
+            registry.register::<RrSceneEmitter>();
+            registry.register::<RrListener>();
+        }
+        registry
+    }
+
+    /// Saves the entities of `world` in a scene, loads it into a new world and spawns its
+    /// bevy-rrise objects.
+    fn round_trip(world: &World) -> World {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let ron = DynamicScene::from_world(world, &registry)
+            .serialize_ron(&registry)
+            .unwrap();
+
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let scene = SceneDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        let mut loaded = World::new();
+        loaded.insert_resource(registry);
+        scene.write_to_world(&mut loaded, &mut default()).unwrap();
+
+        SystemStage::single_threaded()
+            .with_system(spawn_scene_rr_objects)
+            .run(&mut loaded);
+        loaded
+    }
+
+    #[test]
+    fn scene_round_trip() {
+        let mut world = World::new();
+        world.insert_resource(type_registry());
+        world.spawn(RrSceneEmitter {
+            event: "PlayOneShot".to_string(),
+            flags: vec!["EndOfEvent".to_string(), "Marker".to_string()],
+            auto_post: true,
+            despawn_on_silent: true,
+        });
+        world.spawn(RrSceneEmitter::default());
+        world.spawn(RrListener::new(false));
+
+        let mut loaded = round_trip(&world);
+
+        assert_eq!(loaded.query::<&RrSceneEmitter>().iter(&loaded).count(), 0);
+        let emitters: Vec<_> = loaded
+            .query_filtered::<&RrEmitter, With<GlobalTransform>>()
+            .iter(&loaded)
+            .collect();
+        assert_eq!(emitters.len(), 2);
+
+        let rr_e = emitters.iter().find(|rr_e| rr_e.auto_post).unwrap();
+        assert_eq!(unique_id(rr_e.event_id), get_id_from_string("PlayOneShot"));
+        assert_eq!(
+            rr_e.flags,
+            AkCallbackType::AK_EndOfEvent | AkCallbackType::AK_Marker
+        );
+        assert!(rr_e.despawn_on_silent);
+
+        let rr_e = emitters.iter().find(|rr_e| !rr_e.auto_post).unwrap();
+        assert!(matches!(rr_e.event_id, AkID::Name("")));
+        assert_eq!(rr_e.flags, AkCallbackType(0));
+        assert!(!rr_e.despawn_on_silent);
+
+        let listeners: Vec<_> = loaded
+            .query_filtered::<&RrListener, With<GlobalTransform>>()
+            .iter(&loaded)
+            .collect();
+        assert_eq!(listeners.len(), 1);
+        assert!(!listeners[0].is_default());
+    }
+}
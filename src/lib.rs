@@ -5,15 +5,179 @@
 #![doc = include_str!("../README.md")]
 
 use bevy::prelude::*;
-use rrise::{AkCallbackInfo, AkTransform};
-
+use rrise::{AkCallbackInfo, AkCallbackType, AkGameObjectID, AkPlayingID, AkTransform, AkUniqueID};
+
+pub mod bus;
+pub mod callback_queue;
+pub mod category;
+pub mod cosmetic;
+pub mod cue;
+pub mod culling;
+pub mod diagnostics;
+pub mod doppler;
 pub mod emitter_listener;
+pub mod meter;
+pub mod mixer;
+pub mod occlusion;
 pub mod plugin;
+pub mod report;
+pub mod rtpc;
+pub mod sequence;
 pub mod sound_engine;
+pub mod soundbanks_info;
+pub mod soundseed;
+pub mod switch;
+#[cfg(not(wwrelease))]
+pub mod switch_audit;
+pub mod voice_queue;
+pub mod warm_start;
+pub mod zone;
 
 #[derive(Deref, DerefMut)]
 pub struct AkCallbackEvent(pub AkCallbackInfo);
 
+#[derive(Debug, Clone)]
+/// Sent for each [AkCallbackType::AK_MusicSyncUserCue] callback, in addition to the
+/// [AkCallbackEvent].
+///
+/// Lets composers drive gameplay from cues placed in music segments ("drop", "breakdown"...).
+/// The emitter posting the music must request [AkCallbackType::AK_MusicSyncUserCue] in its flags.
+pub struct RrMusicUserCue {
+    /// Name of the cue; empty if the cue has no name.
+    pub cue: String,
+
+    /// Game object on which the music was posted.
+    pub game_obj_id: AkGameObjectID,
+
+    /// Playing ID of the music event.
+    pub playing_id: AkPlayingID,
+}
+
+impl AkCallbackEvent {
+    /// Game object on which the event triggering this callback was posted.
+    ///
+    /// *See also* [emitter_listener::RrEmitter]
+    pub fn game_obj_id(&self) -> AkGameObjectID {
+        match self.0 {
+            AkCallbackInfo::Default { game_obj_id, .. }
+            | AkCallbackInfo::MusicSync { game_obj_id, .. }
+            | AkCallbackInfo::DynamicSequenceItem { game_obj_id, .. }
+            | AkCallbackInfo::Event { game_obj_id, .. }
+            | AkCallbackInfo::Duration { game_obj_id, .. }
+            | AkCallbackInfo::Marker { game_obj_id, .. }
+            | AkCallbackInfo::Midi { game_obj_id, .. }
+            | AkCallbackInfo::MusicPlaylist { game_obj_id, .. }
+            | AkCallbackInfo::SpeakerMatrixVolume { game_obj_id, .. } => game_obj_id,
+        }
+    }
+
+    /// Playing ID of the event triggering this callback, if known.
+    pub fn playing_id(&self) -> Option<AkPlayingID> {
+        match self.0 {
+            AkCallbackInfo::Default { .. } => None,
+            AkCallbackInfo::MusicSync { playing_id, .. }
+            | AkCallbackInfo::DynamicSequenceItem { playing_id, .. }
+            | AkCallbackInfo::Event { playing_id, .. }
+            | AkCallbackInfo::Duration { playing_id, .. }
+            | AkCallbackInfo::Marker { playing_id, .. }
+            | AkCallbackInfo::Midi { playing_id, .. }
+            | AkCallbackInfo::MusicPlaylist { playing_id, .. }
+            | AkCallbackInfo::SpeakerMatrixVolume { playing_id, .. } => Some(playing_id),
+        }
+    }
+
+    /// ID of the event triggering this callback, if this callback carries it.
+    ///
+    /// Music sync callbacks don't; [CallbackChannel::subscribe()](plugin::CallbackChannel::subscribe())
+    /// finds it back from their playing ID.
+    pub fn event_id(&self) -> Option<AkUniqueID> {
+        match self.0 {
+            AkCallbackInfo::Event { event_id, .. }
+            | AkCallbackInfo::Duration { event_id, .. }
+            | AkCallbackInfo::Marker { event_id, .. }
+            | AkCallbackInfo::Midi { event_id, .. }
+            | AkCallbackInfo::MusicPlaylist { event_id, .. } => Some(event_id),
+            _ => None,
+        }
+    }
+
+    /// Kind of notification this callback is, as a single [AkCallbackType] flag.
+    pub fn callback_type(&self) -> AkCallbackType {
+        match self.0 {
+            AkCallbackInfo::Default { callback_type, .. }
+            | AkCallbackInfo::Event { callback_type, .. } => callback_type,
+            AkCallbackInfo::MusicSync {
+                music_sync_type, ..
+            } => music_sync_type,
+            AkCallbackInfo::DynamicSequenceItem { .. } => {
+                AkCallbackType::AK_EndOfDynamicSequenceItem
+            }
+            AkCallbackInfo::Duration { .. } => AkCallbackType::AK_Duration,
+            AkCallbackInfo::Marker { .. } => AkCallbackType::AK_Marker,
+            AkCallbackInfo::Midi { .. } => AkCallbackType::AK_MIDIEvent,
+            AkCallbackInfo::MusicPlaylist { .. } => AkCallbackType::AK_MusicPlaylistSelect,
+            AkCallbackInfo::SpeakerMatrixVolume { .. } => AkCallbackType::AK_SpeakerVolumeMatrix,
+        }
+    }
+
+    /// Label of a marker or name of a music user cue, if this callback carries one.
+    pub fn label(&self) -> Option<&str> {
+        match &self.0 {
+            AkCallbackInfo::Marker { label, .. } => Some(label),
+            AkCallbackInfo::MusicSync { user_cue_name, .. } if !user_cue_name.is_empty() => {
+                Some(user_cue_name)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Common sets of callback flags, convertible into [AkCallbackType].
+///
+/// Everywhere bevy-rrise accepts callback flags, it also accepts these.
+pub enum CallbackPreset {
+    /// End of event only.
+    EndOfEvent,
+
+    /// Markers of the sounds, and end of event.
+    MarkersAndEnd,
+
+    /// All music synchronization notifications (beats, bars, entry/exit, grid, user cues, sync
+    /// points).
+    MusicSync,
+
+    /// Music beats and bars only, typically to animate things in rhythm.
+    BeatsAndBars,
+
+    /// Music user cues only.
+    ///
+    /// *See also* [RrMusicUserCue]
+    UserCues,
+
+    /// Duration of the sounds played, and end of event.
+    DurationAndEnd,
+}
+
+impl From<CallbackPreset> for AkCallbackType {
+    fn from(preset: CallbackPreset) -> Self {
+        match preset {
+            CallbackPreset::EndOfEvent => AkCallbackType::AK_EndOfEvent,
+            CallbackPreset::MarkersAndEnd => {
+                AkCallbackType::AK_Marker | AkCallbackType::AK_EndOfEvent
+            }
+            CallbackPreset::MusicSync => AkCallbackType::AK_MusicSyncAll,
+            CallbackPreset::BeatsAndBars => {
+                AkCallbackType::AK_MusicSyncBeat | AkCallbackType::AK_MusicSyncBar
+            }
+            CallbackPreset::UserCues => AkCallbackType::AK_MusicSyncUserCue,
+            CallbackPreset::DurationAndEnd => {
+                AkCallbackType::AK_Duration | AkCallbackType::AK_EndOfEvent
+            }
+        }
+    }
+}
+
 pub trait ToAkTransform {
     /// Constructs a Wwise transform based on a game engine transform
     fn to_ak_transform(&self) -> AkTransform;
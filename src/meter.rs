@@ -0,0 +1,116 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rrise::query_params::{get_rtpc_value, RtpcValueType};
+use rrise::{AkID, AkRtpcValue};
+
+#[derive(Debug, Clone)]
+struct RrMeter {
+    rtpc: AkID<'static>,
+    floor_db: f32,
+    db: f32,
+    failing: bool,
+}
+
+#[derive(Debug, Default, Clone, Resource)]
+/// Levels of the mix, read each frame from game parameters driven by Meter effects, so that camera
+/// shakes, rumble or UI pulses can follow what is actually heard.
+///
+/// In your Wwise project, insert a Meter effect on the bus to follow (eg, an Explosions bus) and
+/// make it output to a game parameter; its value is then read at the beginning of each frame, in
+/// [CoreStage::PreUpdate].
+///
+/// *Remark* Rrise doesn't bind bus metering callbacks, so a bus can only be followed through a
+/// Meter effect.
+///
+/// ### Example
+/// ```rust,ignore
+/// app.insert_resource(RrMeters::default().with_meter("Explosions", "Meter_Explosions", -48.));
+///
+/// fn shake_camera(meters: Res<RrMeters>, mut shake: ResMut<CameraShake>) {
+///     shake.intensity = meters.level("Explosions");
+/// }
+/// ```
+pub struct RrMeters(HashMap<&'static str, RrMeter>);
+
+impl RrMeters {
+    pub fn with_meter<T: Into<AkID<'static>>>(
+        mut self,
+        name: &'static str,
+        rtpc: T,
+        floor_db: f32,
+    ) -> Self {
+        self.add_meter(name, rtpc, floor_db);
+        self
+    }
+
+    /// Follows the game parameter `rtpc`, output by a Meter effect in dB, as `name`.
+    ///
+    /// Its [Self::level()] is `0.` at `floor_db` and below, and `1.` at 0 dB and above.
+    pub fn add_meter<T: Into<AkID<'static>>>(
+        &mut self,
+        name: &'static str,
+        rtpc: T,
+        floor_db: f32,
+    ) {
+        self.0.insert(
+            name,
+            RrMeter {
+                rtpc: rtpc.into(),
+                floor_db,
+                db: floor_db,
+                failing: false,
+            },
+        );
+    }
+
+    pub fn remove_meter(&mut self, name: &'static str) {
+        self.0.remove(name);
+    }
+
+    /// Level of the meter `name`, in dB; `None` if there is no such meter.
+    pub fn db(&self, name: &str) -> Option<AkRtpcValue> {
+        self.0.get(name).map(|meter| meter.db)
+    }
+
+    /// Level of the meter `name`, between `0.` and `1.`; `0.` if there is no such meter.
+    pub fn level(&self, name: &str) -> f32 {
+        let Some(meter) = self.0.get(name) else {
+            return 0.;
+        };
+        if meter.floor_db >= 0. {
+            return if meter.db >= 0. { 1. } else { 0. };
+        }
+        (1. - meter.db / meter.floor_db).clamp(0., 1.)
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn read_meters(meters: Option<ResMut<RrMeters>>) {
+    let Some(mut meters) = meters else {
+        return;
+    };
+
+    for (name, meter) in meters.0.iter_mut() {
+        match get_rtpc_value(meter.rtpc, None, None, RtpcValueType::Global(0.)) {
+            Ok(RtpcValueType::Global(db)) | Ok(RtpcValueType::Default(db)) => {
+                meter.db = db;
+                meter.failing = false;
+            }
+            Ok(_) => {}
+            Err(akr) => {
+                // Only logged once, as it would otherwise flood the logs each frame
+                if !meter.failing {
+                    error!(
+                        "Couldn't read meter '{}' from RTPC '{}' - {}",
+                        name, meter.rtpc, akr
+                    );
+                }
+                meter.failing = true;
+            }
+        }
+    }
+}
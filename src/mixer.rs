@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::count_set_rtpc;
+use crate::plugin::RriseBasicSettings;
+use bevy::prelude::*;
+use rrise::game_syncs::SetRtpcValue;
+use rrise::AkID;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Resource)]
+/// Player-facing mix controls.
+pub struct RrMixer {
+    streamer_mode: bool,
+    streamer_mode_rtpcs: Vec<AkID<'static>>,
+    save_path: Option<PathBuf>,
+    dirty: bool,
+}
+
+impl RrMixer {
+    pub(crate) fn new(settings: &RriseBasicSettings) -> Self {
+        let streamer_mode = settings
+            .streamer_mode_save_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|saved| saved.trim() == "1")
+            .unwrap_or_default();
+
+        Self {
+            streamer_mode,
+            streamer_mode_rtpcs: settings.streamer_mode_rtpcs.clone(),
+            save_path: settings.streamer_mode_save_path.clone(),
+            dirty: true,
+        }
+    }
+
+    /// Mutes (or unmutes) the music buses bound to
+    /// [RriseBasicSettings::streamer_mode_rtpcs], so that streams don't get flagged for
+    /// copyrighted music.
+    ///
+    /// The choice is saved to [RriseBasicSettings::streamer_mode_save_path] if set. Takes effect
+    /// at the beginning of the next frame.
+    pub fn set_streamer_mode(&mut self, enabled: bool) {
+        if self.streamer_mode == enabled {
+            return;
+        }
+
+        self.streamer_mode = enabled;
+        self.dirty = true;
+
+        if let Some(path) = &self.save_path {
+            if let Err(e) = std::fs::write(path, if enabled { "1" } else { "0" }) {
+                warn!(
+                    "Couldn't save streamer mode to {} - {}",
+                    path.to_string_lossy(),
+                    e
+                );
+            }
+        }
+    }
+
+    pub fn streamer_mode(&self) -> bool {
+        self.streamer_mode
+    }
+}
+
+// Also applies the streamer mode again after a sound engine restart
+pub(crate) fn sync_mixer(mut mixer: ResMut<RrMixer>, mut last_generation: Local<u32>) {
+    let generation = crate::plugin::sound_engine_generation();
+    if !mixer.dirty && *last_generation == generation {
+        return;
+    }
+    *last_generation = generation;
+    mixer.dirty = false;
+
+    let volume = if mixer.streamer_mode { 0. } else { 100. };
+    for &rtpc in mixer.streamer_mode_rtpcs.iter() {
+        count_set_rtpc();
+        if let Err(akr) = SetRtpcValue::new(rtpc, volume)
+            .should_bypass_designer_interp(true)
+            .set()
+        {
+            error!("Couldn't set RTPC '{}' to {} - {}", rtpc, volume, akr);
+        }
+    }
+
+    if !mixer.streamer_mode_rtpcs.is_empty() {
+        debug!(
+            "Streamer mode {}",
+            if mixer.streamer_mode { "on" } else { "off" }
+        );
+    }
+}
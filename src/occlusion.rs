@@ -0,0 +1,171 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::count_set_rtpc;
+use crate::emitter_listener::{ak_id_of, RrEmitter, RrListener, RrObjDesc, RrRegistered};
+use bevy::prelude::*;
+use rrise::game_syncs::SetRtpcValue;
+use rrise::{AkID, AkRtpcValue};
+
+/// Computes how much the path between an emitter and the listener is blocked, usually with
+/// raycasts of your physics engine.
+///
+/// ### Example
+/// ```rust,ignore
+/// struct RapierObstruction;
+///
+/// impl RrObstructionProvider for RapierObstruction {
+///     fn obstruction(&self, world: &World, emitter: Vec3, listener: Vec3) -> (f32, f32) {
+///         let ctx = world.resource::<RapierContext>();
+///         let dir = listener - emitter;
+///         let blocked = ctx
+///             .cast_ray(emitter, dir.normalize(), dir.length(), true, QueryFilter::only_fixed())
+///             .is_some();
+///         (if blocked { 1. } else { 0. }, 0.)
+///     }
+/// }
+///
+/// app.insert_resource(RrObstruction::new(RapierObstruction));
+/// ```
+pub trait RrObstructionProvider: Send + Sync + 'static {
+    /// `(obstruction, occlusion)` of the path from `emitter` to `listener`, both between `0.`
+    /// (clear) and `1.` (fully blocked).
+    fn obstruction(&self, world: &World, emitter: Vec3, listener: Vec3) -> (f32, f32);
+}
+
+#[derive(Resource)]
+/// Drives the obstruction and occlusion of the [RrOcclusion] emitters from an
+/// [RrObstructionProvider], each frame.
+///
+/// Values are smoothed, then set on each emitter for [Self::obstruction_rtpc] and
+/// [Self::occlusion_rtpc], scaled to `0..100`; in your Wwise project, bind these RTPCs to the
+/// volume and low-pass filter of the obstructable sounds.
+///
+/// *Remark* Rrise doesn't bind `AK::SoundEngine::SetObjectObstructionAndOcclusion` yet, so the
+/// obstruction and occlusion curves of the Wwise project settings aren't used.
+pub struct RrObstruction {
+    provider: Box<dyn RrObstructionProvider>,
+
+    /// Time, in seconds, for the values to get about two thirds of the way to their target.
+    ///
+    /// Defaults to `0.1`.
+    pub smoothing: f32,
+
+    /// Defaults to `Rrise_Obstruction`.
+    pub obstruction_rtpc: AkID<'static>,
+
+    /// Defaults to `Rrise_Occlusion`.
+    pub occlusion_rtpc: AkID<'static>,
+}
+
+impl RrObstruction {
+    pub fn new<T: RrObstructionProvider>(provider: T) -> Self {
+        Self {
+            provider: Box::new(provider),
+            smoothing: 0.1,
+            obstruction_rtpc: AkID::Name("Rrise_Obstruction"),
+            occlusion_rtpc: AkID::Name("Rrise_Occlusion"),
+        }
+    }
+
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    pub fn with_rtpcs<T: Into<AkID<'static>>, U: Into<AkID<'static>>>(
+        mut self,
+        obstruction_rtpc: T,
+        occlusion_rtpc: U,
+    ) -> Self {
+        self.obstruction_rtpc = obstruction_rtpc.into();
+        self.occlusion_rtpc = occlusion_rtpc.into();
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, Component)]
+/// Marks the emitter on the same entity as subject to obstruction and occlusion from the
+/// default listener; see [RrObstruction].
+pub struct RrOcclusion {
+    obstruction: f32,
+    occlusion: f32,
+}
+
+impl RrOcclusion {
+    /// Current smoothed obstruction, between `0.` and `1.`.
+    pub fn obstruction(&self) -> f32 {
+        self.obstruction
+    }
+
+    /// Current smoothed occlusion, between `0.` and `1.`.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion
+    }
+}
+
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn update_occlusion(
+    world: &mut World,
+    listeners: &mut QueryState<(&RrListener, &GlobalTransform), With<RrRegistered>>,
+    emitters: &mut QueryState<
+        (Entity, &GlobalTransform),
+        (With<RrOcclusion>, With<RrEmitter>, With<RrRegistered>),
+    >,
+    mut targets: Local<Vec<(Entity, f32, f32)>>,
+) {
+    let Some(settings) = world.get_resource::<RrObstruction>() else {
+        return;
+    };
+    let Some(listener_pos) = listeners
+        .iter(world)
+        .find(|(rr_l, _)| rr_l.is_default())
+        .map(|(_, tfm)| tfm.translation())
+    else {
+        return;
+    };
+
+    let provider = &settings.provider;
+    targets.clear();
+    for (e, tfm) in emitters.iter(world) {
+        let (obstruction, occlusion) = provider.obstruction(world, tfm.translation(), listener_pos);
+        targets.push((e, obstruction.clamp(0., 1.), occlusion.clamp(0., 1.)));
+    }
+
+    let dt = world.resource::<Time>().delta_seconds();
+    let blend = if settings.smoothing > 0. {
+        1. - (-dt / settings.smoothing).exp()
+    } else {
+        1.
+    };
+    let (obstruction_rtpc, occlusion_rtpc) = (settings.obstruction_rtpc, settings.occlusion_rtpc);
+
+    for &(e, obstruction, occlusion) in targets.iter() {
+        let Some(mut rr_occlusion) = world.get_mut::<RrOcclusion>(e) else {
+            continue;
+        };
+        rr_occlusion.obstruction += (obstruction - rr_occlusion.obstruction) * blend;
+        rr_occlusion.occlusion += (occlusion - rr_occlusion.occlusion) * blend;
+
+        let id = ak_id_of(e);
+        for (rtpc, value) in [
+            (obstruction_rtpc, rr_occlusion.obstruction),
+            (occlusion_rtpc, rr_occlusion.occlusion),
+        ] {
+            count_set_rtpc();
+            if let Err(akr) = SetRtpcValue::new(rtpc, value as AkRtpcValue * 100.)
+                .for_target(id)
+                .set()
+            {
+                error!(
+                    "Couldn't set RTPC '{}' on {} - {}",
+                    rtpc,
+                    RrObjDesc::new(Some(e), None),
+                    akr
+                );
+            }
+        }
+    }
+}
@@ -2,20 +2,59 @@
  * Copyright (c) 2022 Contributors to the bevy-rrise project
  */
 
+use crate::bus::{read_soundbanks_metadata, RrBusHierarchy};
+#[cfg(feature = "crossbeam-channel")]
+use crate::callback_queue::RrCrossbeamCallbackQueue;
+#[cfg(not(feature = "crossbeam-channel"))]
+use crate::callback_queue::RrStdCallbackQueue;
+use crate::callback_queue::{RrCallbackQueue, RrEventCallbacks};
+use crate::category::{enforce_category_budgets, RrCategoryBudgets};
+use crate::cosmetic::{update_cosmetic_emitters, RrCosmeticActivation};
+use crate::cue::{apply_audio_cues, RrAudioCue, RrAudioCueLoader};
+use crate::culling::update_distance_culling;
+use crate::doppler::{track_rr_velocities, update_doppler, RrDopplerSettings};
 use crate::emitter_listener::{
-    despawn_silent_emitters, init_new_rr_objects, stop_destroyed_emitters, update_rr_position,
-    RrListenerBundle,
+    apply_event_changes, despawn_on_callbacks, despawn_silent_emitters, init_new_rr_objects,
+    keep_ambiences_playing, reregister_rr_objects, resume_rr_positions, spawn_scene_rr_objects,
+    stop_destroyed_emitters, unique_id, update_listener_relative_positions, update_rr_position,
+    update_temporary_listeners, RrListener, RrListenerBundle, RrSceneEmitter,
 };
-use crate::AkCallbackEvent;
+use crate::meter::read_meters;
+use crate::mixer::{sync_mixer, RrMixer};
+use crate::occlusion::update_occlusion;
+use crate::report::{
+    dump_audio_state, install_panic_hook, snapshot_audio_state, RrCallbackHistory, RrDumpAudioState,
+};
+use crate::rtpc::sync_rtpc_components;
+use crate::sequence::{run_playlists, run_sequences, RrSequenceFinished};
+use crate::sound_engine::{
+    process_bank_load_queue, run_deferred_calls, send_bank_batch_progress, send_bank_events,
+    PostEventAtLocation, RrBankBatchProgressed, RrBankEvent, RrBankLoadQueue, RrBanks,
+};
+use crate::soundbanks_info::RrSoundbanksInfo;
+use crate::soundseed::RrSoundSeed;
+use crate::switch::sync_switch_components;
+use crate::voice_queue::{
+    process_voice_queue, RrVoiceLineFinished, RrVoiceLineStarted, RrVoiceQueue,
+};
+use crate::warm_start::{save_profile, warm_start, RrWarmStart};
+use crate::zone::update_listener_effect_zones;
+use crate::{AkCallbackEvent, RrMusicUserCue};
 use bevy::app::AppExit;
 use bevy::asset::FileAssetIo;
 use bevy::prelude::*;
-use crossbeam_channel::{Receiver, Sender};
+use bevy::transform::TransformSystem;
+use bevy::utils::HashMap;
+use bevy::window::{WindowFocused, WindowId};
 use rrise::settings::*;
 use rrise::*;
 use std::cell::RefCell;
-use std::path::PathBuf;
-use std::sync::{Arc, RwLock};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+#[cfg(not(wwrelease))]
+use std::time::SystemTime;
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemLabel)]
 pub enum RriseLabel {
@@ -33,17 +72,139 @@ pub enum RriseLabel {
     /// frame will be populated with Rrise callbacks that occurred since the last execution of this label.
     RriseCallbackEventsPopulated,
 
-    /// This marks the moment in the frame's [CoreStage::PostUpdate] where the sound engine gets
-    /// terminated if an [AppExit] event occurred. It is not safe to call bevy-rrise APIs and Rrise
+    /// This marks the moment in the frame's [CoreStage::Last] where the sound engine gets
+    /// terminated if an [AppExit] event occurred (see
+    /// [RriseBasicSettings::terminate_on_app_exit]). It is not safe to call bevy-rrise APIs and Rrise
     /// raw APIs from now on.
     RriseMightBeTerminated,
 }
 
+/// Name of the audio device shareset used when [RriseBasicSettings::use_dummy_output] is set.
+const DUMMY_OUTPUT_SHARESET: &str = "No_Output";
+
+/// Incremented each time the sound engine gets (re)initialized.
+static SOUND_ENGINE_GENERATION: AtomicU32 = AtomicU32::new(0);
+
+pub(crate) fn sound_engine_generation() -> u32 {
+    SOUND_ENGINE_GENERATION.load(Ordering::Acquire)
+}
+
+/// Whether Init.bnk is loaded in the current sound engine.
+static INIT_BANK_LOADED: AtomicBool = AtomicBool::new(false);
+
+pub(crate) fn is_init_bank_loaded() -> bool {
+    INIT_BANK_LOADED.load(Ordering::Acquire)
+}
+
+/// See [RriseBasicSettings::track_durations].
+static TRACK_DURATIONS: AtomicBool = AtomicBool::new(true);
+
+pub(crate) fn tracks_durations() -> bool {
+    TRACK_DURATIONS.load(Ordering::Relaxed)
+}
+
+/// See [RriseBasicSettings::default_callback_flags].
+static DEFAULT_CALLBACK_FLAGS: AtomicU32 = AtomicU32::new(0);
+
+pub(crate) fn default_callback_flags() -> AkCallbackType {
+    AkCallbackType(DEFAULT_CALLBACK_FLAGS.load(Ordering::Relaxed))
+}
+
+/// See [RrEngine::set_enabled()].
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What to do when rendering audio fails mid-session (audio device lost, internal failure...).
+///
+/// Whatever the strategy, an [RrAudioDeviceLost] event is sent when rendering starts failing.
+pub enum RrRenderErrorStrategy {
+    /// Only log the error, once until rendering succeeds again.
+    LogOnly,
+
+    /// Restart the sound engine with the same settings.
+    ///
+    /// Init.bnk and the banks loaded with [load_bank()](crate::sound_engine::load_bank()) are
+    /// loaded again, Wwise states and global RTPC values are restored, and all registered
+    /// emitters and listeners are registered again. Voices that were playing are lost, as well as
+    /// switches set at runtime; banks loaded with Rrise directly must be reloaded when receiving
+    /// [RrAudioDeviceLost].
+    ///
+    /// If `fallback_to_dummy_output` is `true` and the restart fails, restart again rendering to
+    /// the `No_Output` shareset so that the game keeps running (see
+    /// [RriseBasicSettings::use_dummy_output]).
+    ///
+    /// Failed restarts, or restarts after which rendering still fails, are retried with a delay
+    /// doubling each time, up to 30 seconds. [RrAudioDeviceLost] is sent after the first attempt,
+    /// then again once a restart succeeds.
+    Restart { fallback_to_dummy_output: bool },
+}
+
+#[derive(Debug, Clone)]
+/// Sent when rendering audio starts failing.
+///
+/// *See also* [RriseBasicSettings::render_error_strategy]
+pub struct RrAudioDeviceLost {
+    /// Error returned by the sound engine when rendering.
+    pub error: AkResult,
+
+    /// Whether the sound engine was successfully restarted.
+    pub restarted: bool,
+
+    /// Whether the sound engine now renders to the `No_Output` shareset.
+    pub dummy_output: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+/// Window considered as the game view for audio focus purposes.
+///
+/// Only focus changes of this window trigger [RrAudioFocusChanged]; in tools or editors with
+/// several windows, focusing another window of the app doesn't count as losing focus.
+///
+/// Defaults to the primary window.
+pub struct RrAudioFocusWindow(pub WindowId);
+
+impl Default for RrAudioFocusWindow {
+    fn default() -> Self {
+        Self(WindowId::primary())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Sent when the [RrAudioFocusWindow] gains or loses focus, or when another window gets
+/// designated.
+///
+/// React to it to mute or pause your audio the way your Wwise project allows it (a state, a
+/// global RTPC driving the master bus volume...).
+pub struct RrAudioFocusChanged {
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone, Resource)]
+/// Overrides [RriseBasicSettings::init_language] when inserted before the sound engine gets
+/// initialized; for instance from a command line argument.
+///
+/// ### Example
+/// ```rust,ignore
+/// let mut app = App::new();
+/// if let Some(language) = std::env::args().skip_while(|arg| arg != "--language").nth(1) {
+///     app.insert_resource(RrInitLanguage(language));
+/// }
+/// ```
+pub struct RrInitLanguage(pub String);
+
 #[derive(Debug, Clone)]
 /// Plugin basic settings
 pub struct RriseBasicSettings {
     /// One of the languages supported by your Wwise project in Project > Languages.
     ///
+    /// Can be overridden at launch without code changes, to test other voice-over languages: by an
+    /// [RrInitLanguage] resource inserted before the app runs, or by the `RRISE_LANGUAGE`
+    /// environment variable, which wins over both.
+    ///
     /// Defaults to English(US).
     pub init_language: String,
 
@@ -51,9 +212,20 @@ pub struct RriseBasicSettings {
     /// If the path given is absolute, overrides the asset server folder by that one.
     ///
     /// Don't add the platform folder; just the root where to expect to find the Windows or Linux
-    /// folder containing the banks.
+    /// folder containing the banks (see [Self::platform_folder]).
     pub banks_location: PathBuf,
 
+    /// Folder of [Self::banks_location] containing the banks generated for the current platform,
+    /// for projects whose platforms aren't named after the OS.
+    ///
+    /// Defaults to `None`: `Windows` or `Linux`, depending on the OS.
+    pub platform_folder: Option<PathBuf>,
+
+    /// File name of the initialization bank, always loaded first.
+    ///
+    /// Defaults to `Init.bnk`.
+    pub init_bank: String,
+
     /// Whether to create a default listener automatically.
     ///
     /// If this is `true`, it is available after [RriseLabel::RriseReady].
@@ -76,6 +248,376 @@ pub struct RriseBasicSettings {
     /// }
     /// ```
     pub spawn_default_listener: bool,
+
+    /// Whether to render audio to the `No_Output` audio device shareset instead of the system
+    /// output.
+    ///
+    /// Banks, events and callbacks keep working as usual, but nothing reaches the audio hardware.
+    /// Useful to run examples or tests on machines without audio devices (CI for instance).
+    /// The `No_Output` shareset is present by default in Wwise projects; it must be part of your
+    /// Init.bnk.
+    ///
+    /// Setting the `RRISE_DUMMY_OUTPUT` environment variable (to anything but `0`) also enables
+    /// this, without code changes.
+    ///
+    /// Defaults to `false`.
+    pub use_dummy_output: bool,
+
+    /// How to react when rendering audio fails mid-session.
+    ///
+    /// Defaults to [RrRenderErrorStrategy::LogOnly].
+    pub render_error_strategy: RrRenderErrorStrategy,
+
+    /// Folder where Wwise looks for plugin shared libraries (DLLs or SOs) that were not
+    /// statically linked.
+    ///
+    /// If `None`, the path set with [AkInitSettings::with_plugin_dll_path()] (if any) is kept.
+    ///
+    /// Defaults to [default_plugin_dll_path()].
+    pub plugin_dll_path: Option<PathBuf>,
+
+    /// How long to keep rendering audio after Bevy's [AppExit] event, before terminating the
+    /// sound engine.
+    ///
+    /// Gives fades already scheduled (and the [Self::shutdown_event]) a chance to be heard, so
+    /// that quitting doesn't click or pop. The app is blocked during this period.
+    ///
+    /// Defaults to no grace period.
+    pub shutdown_grace_period: Duration,
+
+    /// Event posted when Bevy's [AppExit] event is received, typically to fade out the master
+    /// bus during [Self::shutdown_grace_period].
+    ///
+    /// Defaults to `None`.
+    pub shutdown_event: Option<AkID<'static>>,
+
+    /// Whether to terminate the sound engine when Bevy's [AppExit] event is received.
+    ///
+    /// Unset it if your app manages shutdown itself (custom runner, editor embedding the game):
+    /// call [RrEngine::terminate()] once you're done updating the app instead.
+    ///
+    /// Defaults to `true`.
+    pub terminate_on_app_exit: bool,
+
+    /// If set, the mix ramps from silence to full volume over this duration once Init.bnk is
+    /// loaded, avoiding a full-volume burst when lots of emitters start playing in the first
+    /// frames.
+    ///
+    /// The ramp is done by interpolating the global [Self::master_fade_rtpc] from `0` to `100`;
+    /// in your Wwise project, bind this RTPC to the volume of your master bus.
+    ///
+    /// Defaults to `None`.
+    pub startup_fade_in: Option<Duration>,
+
+    /// Global RTPC (ranging from `0` to `100`) that bevy-rrise drives to fade the whole mix.
+    ///
+    /// Defaults to `Rrise_MasterFade`.
+    pub master_fade_rtpc: AkID<'static>,
+
+    /// Whether to pick free ports for the profiler communication if the ones in
+    /// [AkCommSettings] are already taken, for instance by another instance of your game
+    /// running on the same machine.
+    ///
+    /// The ports used are logged and available in the [RrCommPorts] resource, so that you can
+    /// connect the Wwise profiler to a specific instance by IP and port. Ignored in `wwrelease`.
+    ///
+    /// Defaults to `false`.
+    pub auto_pick_comm_ports: bool,
+
+    /// Global RTPCs (ranging from `0` to `100`) muted by the streamer mode; in your Wwise project,
+    /// bind each of them to the volume of a music bus to silence in streams.
+    ///
+    /// *See also* [RrMixer::set_streamer_mode()](crate::mixer::RrMixer::set_streamer_mode())
+    ///
+    /// Defaults to an empty list.
+    pub streamer_mode_rtpcs: Vec<AkID<'static>>,
+
+    /// File where the streamer mode choice is saved, and restored from at startup.
+    ///
+    /// Defaults to `None`: the choice isn't persisted.
+    pub streamer_mode_save_path: Option<PathBuf>,
+
+    /// Whether to request [AkCallbackType::AK_Duration] callbacks on every post from an
+    /// [RrEmitter](crate::emitter_listener::RrEmitter), to keep the estimated duration of its
+    /// [playing events](crate::emitter_listener::RrEmitter::playing_events()) up to date.
+    ///
+    /// These callbacks are only forwarded as [AkCallbackEvent]s if you asked for them.
+    ///
+    /// Defaults to `true`.
+    pub track_durations: bool,
+
+    /// Callback flags used by posts passing a [CallbackChannel] but no flags, so that you don't
+    /// have to repeat them at every call site.
+    ///
+    /// *See also* [CallbackPreset](crate::CallbackPreset)
+    ///
+    /// Defaults to none (ie, `AkCallbackType(0)`).
+    pub default_callback_flags: AkCallbackType,
+
+    /// Whether to print an [RrAudioState](crate::report::RrAudioState) report (refreshed every
+    /// second) to stderr if the app panics.
+    ///
+    /// Defaults to `false`.
+    pub dump_state_on_panic: bool,
+
+    /// Whether to reload the soundbanks when they get regenerated from the Wwise authoring tool
+    /// while the game is running; ignored in `wwrelease`.
+    ///
+    /// The banks folder is polled every second. Once regenerated files stop changing, the sound
+    /// engine is restarted (Rrise can't unload a single bank), Init.bnk and the banks loaded with
+    /// [load_bank()](crate::sound_engine::load_bank()) are loaded again, Wwise states and global
+    /// RTPC values are restored, emitters are registered again and [RrBanksReloaded] is sent.
+    /// Auto posted events and [RrLoopingAmbience]s are restarted; post your other events again
+    /// when receiving [RrBanksReloaded].
+    ///
+    /// [RrLoopingAmbience]: crate::emitter_listener::RrLoopingAmbience
+    ///
+    /// Defaults to `false`.
+    pub hot_reload_banks: bool,
+}
+
+#[derive(Debug, Clone)]
+/// Sent when the soundbanks got reloaded after being regenerated; see
+/// [RriseBasicSettings::hot_reload_banks].
+pub struct RrBanksReloaded {
+    /// Bank files that changed.
+    pub changed: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+/// Ports actually used for the profiler communication; not available in `wwrelease`.
+///
+/// *See also* [RriseBasicSettings::auto_pick_comm_ports]
+pub struct RrCommPorts {
+    /// UDP port Wwise authoring discovers running games on.
+    pub discovery_broadcast: AkPortNumber,
+
+    /// TCP port the Wwise profiler connects to.
+    pub command: AkPortNumber,
+}
+
+#[derive(Debug, Clone, Resource)]
+/// Versions bevy-rrise runs with; include them when reporting issues.
+///
+/// Its [Display] implementation gives a one line summary, also appended to bevy-rrise
+/// unexpected error logs.
+pub struct RrVersion {
+    /// Version of this crate.
+    pub bevy_rrise: &'static str,
+
+    /// Version of the soundbanks format the Wwise bindings were generated for. Banks generated
+    /// by another Wwise version can't be loaded.
+    pub soundbank_version: u32,
+
+    /// Wwise SDK version linked, as guessed from the `WWISESDK` environment variable when
+    /// bevy-rrise was built (usually pointing in a folder named like `Wwise 2021.1.10.7883`).
+    pub wwise_sdk: Option<String>,
+
+    /// Wwise configuration linked: `Debug`, `Profile` or `Release`.
+    pub configuration: &'static str,
+}
+
+impl RrVersion {
+    const CONFIGURATION: &'static str = if cfg!(wwdebug) {
+        "Debug"
+    } else if cfg!(wwrelease) {
+        "Release"
+    } else {
+        "Profile"
+    };
+
+    fn detect() -> Self {
+        // The SDK Rrise was linked against, not the one of the environment running the game
+        let wwise_sdk = option_env!("WWISESDK").and_then(|sdk| {
+            Path::new(sdk).iter().rev().find_map(|component| {
+                let component = component.to_string_lossy();
+                let version = component.trim_start_matches("Wwise").trim();
+                let is_version = version.len() > 5
+                    && version
+                        .get(..4)
+                        .is_some_and(|year| year.bytes().all(|b| b.is_ascii_digit()))
+                    && version[4..].starts_with('.');
+                is_version.then(|| version.to_string())
+            })
+        });
+
+        Self {
+            bevy_rrise: env!("CARGO_PKG_VERSION"),
+            soundbank_version: AK_SOUNDBANK_VERSION,
+            wwise_sdk,
+            configuration: Self::CONFIGURATION,
+        }
+    }
+}
+
+impl std::fmt::Display for RrVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bevy-rrise {}, Wwise SDK {} ({}, soundbank v{})",
+            self.bevy_rrise,
+            self.wwise_sdk.as_deref().unwrap_or("unknown"),
+            self.configuration,
+            self.soundbank_version
+        )
+    }
+}
+
+#[derive(Debug, Clone, Resource)]
+/// Runtime switch for the whole audio side of bevy-rrise.
+///
+/// While disabled, the mix is muted through [RriseBasicSettings::master_fade_rtpc], emitter and
+/// listener positions aren't synced anymore, and posting does nothing but return
+/// [AK_INVALID_PLAYING_ID]. Useful for a "music off" streamer mode or to profile the audio overhead.
+///
+/// The mute is applied again after sound engine restarts, and holds back the
+/// [RriseBasicSettings::startup_fade_in], which resumes when re-enabling.
+///
+/// *Remark* What was playing when disabling keeps playing silently.
+pub struct RrEngine {
+    master_fade_rtpc: AkID<'static>,
+    fade_in_end: Option<Instant>,
+    shutdown_event: Option<AkID<'static>>,
+    shutdown_grace_period: Duration,
+}
+
+impl RrEngine {
+    /// Disables or re-enables audio; re-enabling syncs all positions again on the next frame.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if ENABLED.swap(enabled, Ordering::AcqRel) == enabled {
+            return;
+        }
+
+        if enabled {
+            // Finish the startup fade in if it got interrupted
+            let remaining = self
+                .fade_in_end
+                .map(|end| end.saturating_duration_since(Instant::now()))
+                .unwrap_or_default();
+            self.set_master_fade(100., remaining);
+        } else {
+            self.set_master_fade(0., Duration::ZERO);
+        }
+        debug!("Audio {}", if enabled { "enabled" } else { "disabled" });
+    }
+
+    /// Sets [RriseBasicSettings::master_fade_rtpc] to `volume`, interpolating over `duration`.
+    fn set_master_fade(&self, volume: AkRtpcValue, duration: Duration) {
+        if let Err(akr) = game_syncs::SetRtpcValue::new(self.master_fade_rtpc, volume)
+            .with_interp_millis(duration.as_millis() as AkTimeMs)
+            .should_bypass_designer_interp(true)
+            .set()
+        {
+            error!(
+                "Couldn't set RTPC '{}' to {} - {}",
+                self.master_fade_rtpc, volume, akr
+            );
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        is_enabled()
+    }
+
+    /// Terminates the sound engine, after posting [RriseBasicSettings::shutdown_event] and
+    /// rendering audio for [RriseBasicSettings::shutdown_grace_period].
+    ///
+    /// This is what happens on Bevy's [AppExit] event, unless
+    /// [RriseBasicSettings::terminate_on_app_exit] is unset. Don't update the app afterwards:
+    /// it is not safe to call bevy-rrise APIs and Rrise raw APIs anymore.
+    ///
+    /// Does nothing if the sound engine isn't initialized.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// app.set_runner(|mut app| {
+    ///     while !editor_closed() {
+    ///         app.update();
+    ///     }
+    ///     app.world.resource_mut::<RrEngine>().terminate().unwrap();
+    /// });
+    /// ```
+    pub fn terminate(&mut self) -> Result<(), AkResult> {
+        if !sound_engine::is_initialized() {
+            return Ok(());
+        }
+
+        if let Some(event) = self.shutdown_event {
+            if let Err(akr) = PostEventAtLocation::new(event, Transform::default()).post(None) {
+                error!("Couldn't post shutdown event '{}' - {}", event, akr);
+            }
+        }
+
+        let grace_start = Instant::now();
+        while grace_start.elapsed() < self.shutdown_grace_period {
+            sound_engine::render_audio(ALLOW_SYNC_RENDER)?;
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        save_profile();
+        term_sound_engine()
+    }
+}
+
+/// Finds where Wwise plugin shared libraries are most likely to be.
+///
+/// That's the folder of the executable if it contains Wwise plugins (which is what you want when
+/// deploying your game), otherwise the `bin` folder of the Wwise SDK pointed at by the `WWISESDK`
+/// environment variable, matching the current platform and Wwise configuration.
+///
+/// Returns `None` if none of these folders exists.
+///
+/// *Remark* On Windows, Wwise can't discover DLLs in a path containing spaces.
+///
+/// *Remark* Wwise is a licensed product: you need a proper license to deploy its plugins along
+/// your executable.
+pub fn default_plugin_dll_path() -> Option<PathBuf> {
+    if let Some(exe_dir) = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+    {
+        if contains_wwise_plugins(&exe_dir) {
+            return Some(exe_dir);
+        }
+    }
+
+    let wwise_sdk = PathBuf::from(std::env::var_os("WWISESDK")?);
+
+    // Use the most recent MSVC toolset installed in the SDK
+    #[cfg(windows)]
+    let platform = std::fs::read_dir(&wwise_sdk)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("x64_vc"))
+        .max()?;
+    #[cfg(target_os = "linux")]
+    let platform = "Linux_x64";
+
+    let path = wwise_sdk
+        .join(platform)
+        .join(RrVersion::CONFIGURATION)
+        .join("bin");
+    path.is_dir().then_some(path)
+}
+
+fn contains_wwise_plugins(dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+
+    entries.filter_map(|entry| entry.ok()).any(|entry| {
+        let path = entry.path();
+        let is_shared_lib = matches!(
+            path.extension().and_then(|ext| ext.to_str()),
+            Some("dll") | Some("so")
+        );
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        is_shared_lib && stem.trim_start_matches("lib").starts_with("Ak")
+    })
 }
 
 impl Default for RriseBasicSettings {
@@ -85,7 +627,24 @@ impl Default for RriseBasicSettings {
         Self {
             init_language: "English(US)".to_string(),
             banks_location: PathBuf::from("soundbanks"),
+            platform_folder: None,
+            init_bank: "Init.bnk".to_string(),
             spawn_default_listener: true,
+            use_dummy_output: false,
+            render_error_strategy: RrRenderErrorStrategy::LogOnly,
+            plugin_dll_path: default_plugin_dll_path(),
+            shutdown_grace_period: Duration::ZERO,
+            shutdown_event: None,
+            terminate_on_app_exit: true,
+            startup_fade_in: None,
+            master_fade_rtpc: AkID::Name("Rrise_MasterFade"),
+            auto_pick_comm_ports: false,
+            streamer_mode_rtpcs: vec![],
+            streamer_mode_save_path: None,
+            track_durations: true,
+            default_callback_flags: AkCallbackType(0),
+            dump_state_on_panic: false,
+            hot_reload_banks: false,
         }
     }
 }
@@ -214,9 +773,62 @@ impl Plugin for RrisePlugin {
             plugin_settings.write().unwrap().bevy_asset_folder = asset_folder.clone();
         }
 
-        app.add_event::<AkCallbackEvent>()
+        let version = RrVersion::detect();
+        debug!("Starting {}", version);
+
+        let engine = {
+            let settings = &plugin_settings.read().unwrap().plugin;
+            RrEngine {
+                master_fade_rtpc: settings.master_fade_rtpc,
+                fade_in_end: None,
+                shutdown_event: settings.shutdown_event,
+                shutdown_grace_period: settings.shutdown_grace_period,
+            }
+        };
+        let mixer = RrMixer::new(&plugin_settings.read().unwrap().plugin);
+        let banks = RrBanks::new(gen_banks_folder(&plugin_settings.read().unwrap()));
+        TRACK_DURATIONS.store(
+            plugin_settings.read().unwrap().plugin.track_durations,
+            Ordering::Relaxed,
+        );
+        DEFAULT_CALLBACK_FLAGS.store(
+            plugin_settings
+                .read()
+                .unwrap()
+                .plugin
+                .default_callback_flags
+                .0,
+            Ordering::Relaxed,
+        );
+
+        #[cfg(not(wwrelease))]
+        if plugin_settings.read().unwrap().plugin.hot_reload_banks {
+            app.add_system_to_stage(CoreStage::First, hot_reload_banks.pipe(error_handler));
+        }
+
+        #[cfg(not(wwrelease))]
+        app.add_system_to_stage(CoreStage::PreUpdate, refresh_comm_ports);
+
+        if plugin_settings.read().unwrap().plugin.dump_state_on_panic {
+            install_panic_hook();
+            app.add_system_to_stage(CoreStage::Last, snapshot_audio_state);
+        }
+
+        app.insert_resource(version)
+            .insert_resource(engine)
+            .insert_resource(mixer)
+            .add_event::<RrAudioDeviceLost>()
+            .add_event::<RrAudioFocusChanged>()
+            .add_event::<RrBanksReloaded>()
+            .add_event::<RrBankEvent>()
+            .add_event::<RrBankBatchProgressed>()
+            .insert_resource(banks)
+            .init_resource::<RrBankLoadQueue>()
+            .init_resource::<RrAudioFocusWindow>()
             .insert_resource(plugin_settings)
-            .insert_resource(CallbackChannel::new())
+            .add_asset::<RrAudioCue>()
+            .init_asset_loader::<RrAudioCueLoader>()
+            .add_plugin(RriseWorldPlugin)
             .add_startup_system_to_stage(
                 StartupStage::PreStartup,
                 init_sound_engine
@@ -230,28 +842,182 @@ impl Plugin for RrisePlugin {
                     .after(RriseLabel::SoundEngineInitialized)
                     .label(RriseLabel::RriseReady),
             )
+            .add_system_to_stage(CoreStage::PreUpdate, track_audio_focus)
+            .add_system_to_stage(CoreStage::PreUpdate, sync_mixer)
+            .add_system_to_stage(CoreStage::PreUpdate, keep_disabled_engine_muted)
+            .add_system_to_stage(CoreStage::PreUpdate, read_meters)
+            .add_system_to_stage(CoreStage::PreUpdate, process_bank_load_queue)
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                run_deferred_calls.after(RriseLabel::RriseCallbackEventsPopulated),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                send_bank_events.after(process_bank_load_queue),
+            )
+            .add_system_to_stage(CoreStage::PreUpdate, send_bank_batch_progress)
+            .add_system_to_stage(
+                CoreStage::Last,
+                audio_rendering
+                    .pipe(error_handler)
+                    .label(RriseLabel::RriseMightBeTerminated),
+            );
+    }
+}
+
+/// ECS side of bevy-rrise: emitters and listeners management, and callback events.
+///
+/// [RrisePlugin] already adds this to your app; only add it yourself to a [SubApp](bevy::app::SubApp)
+/// (or any other app sharing the process) whose world should own emitters and listeners too.
+/// The sound engine itself is a process-wide singleton that remains owned by the app the
+/// [RrisePlugin] was added to: it is initialized, rendered and terminated from that app's
+/// schedule only.
+///
+/// Each world gets its own [CallbackChannel]. Callbacks of events posted from a world are sent
+/// to the channel of that world and end up in that world's [AkCallbackEvent] queue.
+///
+/// *Remark* Wwise game object IDs are derived from entity indices. Entities with the same index
+/// in two worlds map to the same game object: make sure only one world holds [RrEmitter]s and
+/// [RrListener]s at a time, or hand them off by despawning them in one world before spawning
+/// them in the other.
+///
+/// [RrCue](crate::cue::RrCue)s are only applied in worlds where the [RrAudioCue] asset is
+/// registered before adding this plugin.
+///
+/// [RrEmitter]: crate::emitter_listener::RrEmitter
+/// [RrListener]: crate::emitter_listener::RrListener
+pub struct RriseWorldPlugin;
+
+impl Plugin for RriseWorldPlugin {
+    fn build(&self, app: &mut App) {
+        // Keep a channel inserted by the user, with their own callback queue
+        if !app.world.contains_resource::<CallbackChannel>() {
+            app.insert_resource(CallbackChannel::new());
+        }
+
+        // The RrisePlugin registers the cue asset; other worlds need AssetPlugin and
+        // `add_asset::<RrAudioCue>()` before this plugin to use cues
+        if app.world.contains_resource::<Assets<RrAudioCue>>() {
+            app.add_system_to_stage(
+                CoreStage::PreUpdate,
+                apply_audio_cues.before("Rrise_init_new_rr_objects"),
+            );
+        }
+
+        app.add_event::<AkCallbackEvent>()
+            .add_event::<RrMusicUserCue>()
+            .add_event::<RrVoiceLineStarted>()
+            .add_event::<RrVoiceLineFinished>()
+            .add_event::<RrDumpAudioState>()
+            .add_event::<RrSequenceFinished>()
+            .init_resource::<RrVoiceQueue>()
+            .init_resource::<RrCategoryBudgets>()
+            .init_resource::<RrCosmeticActivation>()
+            .init_resource::<RrDopplerSettings>()
+            .init_resource::<RrSoundSeed>()
+            .register_type::<RrListener>()
+            .register_type::<RrSceneEmitter>()
+            .init_resource::<RrCallbackHistory>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                spawn_scene_rr_objects.before("Rrise_init_new_rr_objects"),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                reregister_rr_objects.before("Rrise_init_new_rr_objects"),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                enforce_category_budgets.before("Rrise_init_new_rr_objects"),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                update_cosmetic_emitters.before("Rrise_init_new_rr_objects"),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                update_distance_culling.before("Rrise_init_new_rr_objects"),
+            )
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 init_new_rr_objects
                     .pipe(error_handler)
+                    .label("Rrise_init_new_rr_objects")
                     .before(RriseLabel::RriseCallbackEventsPopulated),
             )
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 process_callbacks.label(RriseLabel::RriseCallbackEventsPopulated),
             )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                keep_ambiences_playing.after("Rrise_init_new_rr_objects"),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                apply_event_changes
+                    .after("Rrise_init_new_rr_objects")
+                    .before(RriseLabel::RriseCallbackEventsPopulated),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                run_sequences
+                    .after("Rrise_init_new_rr_objects")
+                    .after(RriseLabel::RriseCallbackEventsPopulated),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                run_playlists
+                    .after("Rrise_init_new_rr_objects")
+                    .after(RriseLabel::RriseCallbackEventsPopulated),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                process_voice_queue
+                    .after("Rrise_init_new_rr_objects")
+                    .after(RriseLabel::RriseCallbackEventsPopulated),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 stop_destroyed_emitters
                     .pipe(error_handler)
                     .before("Rrise_despawn_silent_emitters"), // No need to stop silent emitters despawned this frame
             )
+            .add_system_to_stage(CoreStage::Last, dump_audio_state)
+            // In Update so that stop_destroyed_emitters() sees the despawned emitters this frame
+            .add_system_to_stage(CoreStage::Update, despawn_on_callbacks)
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 despawn_silent_emitters
                     .pipe(error_handler)
                     .label("Rrise_despawn_silent_emitters"),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                resume_rr_positions.before("Rrise_despawn_silent_emitters"),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_temporary_listeners.pipe(error_handler),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_listener_effect_zones.after(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_occlusion.after(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                track_rr_velocities.after(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_doppler.after(track_rr_velocities),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, sync_rtpc_components)
+            .add_system_to_stage(CoreStage::PostUpdate, sync_switch_components)
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 update_rr_position
@@ -259,51 +1025,561 @@ impl Plugin for RrisePlugin {
                     .after("Rrise_despawn_silent_emitters"), // No need to stop silent emitters despawned this frame,
             )
             .add_system_to_stage(
-                CoreStage::Last,
-                audio_rendering
+                CoreStage::PostUpdate,
+                update_listener_relative_positions
                     .pipe(error_handler)
-                    .label(RriseLabel::RriseMightBeTerminated),
+                    .after("Rrise_despawn_silent_emitters")
+                    .after(TransformSystem::TransformPropagate),
             );
+
+        #[cfg(not(any(wwrelease, feature = "strip-names")))]
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            crate::emitter_listener::rename_rr_objects.after("Rrise_init_new_rr_objects"),
+        );
+
+        #[cfg(debug_assertions)]
+        app.add_system_to_stage(
+            CoreStage::Last,
+            crate::diagnostics::report_growing_collections,
+        )
+        .add_system_to_stage(
+            CoreStage::Last,
+            crate::diagnostics::watch_never_ending_events,
+        );
     }
 }
 
 #[derive(Clone, Resource)]
 /// Resource to query in systems where you want to post callback-enabled events.
 ///
+/// Callbacks wait in an [RrCallbackQueue] until they get processed; insert a channel created with
+/// [Self::with_queue()] before adding the plugins to pick another backend than the default one.
+///
 /// *See also* [RrEmitter::post_associated_event()](crate::emitter_listener::RrEmitter::post_associated_event())
 pub struct CallbackChannel {
-    pub(crate) sender: Sender<AkCallbackInfo>,
-    receiver: Receiver<AkCallbackInfo>,
+    queue: Arc<dyn RrCallbackQueue>,
+    subscriptions: Arc<Mutex<Vec<RrSubscription>>>,
+    subscribed_playing_ids: Arc<Mutex<HashMap<AkPlayingID, AkUniqueID>>>,
+    pub(crate) closures: Arc<Mutex<HashMap<AkPlayingID, RrCallbackClosure>>>,
 }
 
+/// Closure reacting to the callbacks of a single playing event, on the Bevy side.
+///
+/// *See also* [RrEmitter::post_with_closure()](crate::emitter_listener::RrEmitter::post_with_closure())
+pub type RrCallbackClosure = Box<dyn FnMut(&mut Commands, &AkCallbackInfo) + Send + Sync>;
+
+/// Event ID and callbacks of an [RrEventCallbacks], dead once it's dropped.
+type RrSubscription = (AkUniqueID, Weak<Mutex<Vec<AkCallbackInfo>>>);
+
 impl CallbackChannel {
     fn new() -> Self {
-        let (sender, receiver) = crossbeam_channel::unbounded();
-        Self { sender, receiver }
+        #[cfg(feature = "crossbeam-channel")]
+        return Self::with_queue(RrCrossbeamCallbackQueue::default());
+        #[cfg(not(feature = "crossbeam-channel"))]
+        return Self::with_queue(RrStdCallbackQueue::default());
+    }
+
+    pub fn with_queue<T: RrCallbackQueue>(queue: T) -> Self {
+        Self {
+            queue: Arc::new(queue),
+            subscriptions: default(),
+            subscribed_playing_ids: default(),
+            closures: default(),
+        }
+    }
+
+    /// Sets the callbacks of `event` apart in the returned [RrEventCallbacks], as long as it
+    /// lives.
+    ///
+    /// Only events posted with this channel after subscribing are followed; the usual callback
+    /// flags still decide which callbacks they send.
+    pub fn subscribe<'a, T: Into<AkID<'a>>>(&self, event: T) -> RrEventCallbacks {
+        let subscription = RrEventCallbacks {
+            event_id: unique_id(event.into()),
+            callbacks: default(),
+        };
+        self.subscriptions.lock().unwrap().push((
+            subscription.event_id,
+            Arc::downgrade(&subscription.callbacks),
+        ));
+        subscription
+    }
+
+    /// Whether the callbacks of `event` are set apart by a subscription.
+    pub(crate) fn is_subscribed(&self, event: AkID) -> bool {
+        let subscriptions = self.subscriptions.lock().unwrap();
+        if subscriptions.is_empty() {
+            return false;
+        }
+
+        let event_id = unique_id(event);
+        subscriptions.iter().any(|(id, _)| *id == event_id)
+    }
+
+    /// Remembers the event of `playing_id` if it is subscribed to, as not all callbacks carry
+    /// their event ID.
+    ///
+    /// Its callbacks, [AkCallbackType::AK_EndOfEvent] included, must be sent to this channel:
+    /// the end of the event is what makes the channel forget about `playing_id`.
+    pub(crate) fn track(&self, playing_id: AkPlayingID, event: AkID) {
+        if self.is_subscribed(event) {
+            self.subscribed_playing_ids
+                .lock()
+                .unwrap()
+                .insert(playing_id, unique_id(event));
+        }
+    }
+
+    /// Forgets the playing IDs of events posted before a sound engine restart, which will never
+    /// send their [AkCallbackType::AK_EndOfEvent].
+    pub(crate) fn forget_tracked_playing_ids(&self) {
+        self.subscribed_playing_ids.lock().unwrap().clear();
+    }
+
+    /// Copies `cb_event` to the subscriptions to its event.
+    fn dispatch(&self, cb_event: &AkCallbackEvent) {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        if subscriptions.is_empty() {
+            return;
+        }
+
+        let mut playing_ids = self.subscribed_playing_ids.lock().unwrap();
+        let tracked_event_id = match cb_event.playing_id() {
+            Some(playing_id) if cb_event.callback_type() == AkCallbackType::AK_EndOfEvent => {
+                playing_ids.remove(&playing_id)
+            }
+            Some(playing_id) => playing_ids.get(&playing_id).copied(),
+            None => None,
+        };
+        let Some(event_id) = cb_event.event_id().or(tracked_event_id) else {
+            return;
+        };
+
+        subscriptions.retain(|(id, callbacks)| {
+            let Some(callbacks) = callbacks.upgrade() else {
+                return false;
+            };
+            if *id == event_id {
+                callbacks.lock().unwrap().push(cb_event.0.clone());
+            }
+            true
+        });
+    }
+
+    /// Queues `cb_info` to be processed on the Bevy side; gives it back if it couldn't be.
+    pub(crate) fn send(&self, cb_info: AkCallbackInfo) -> Result<(), AkCallbackInfo> {
+        self.queue.push(cb_info)
+    }
+
+    /// Callbacks received but not processed yet.
+    #[cfg(debug_assertions)]
+    pub(crate) fn pending_callbacks(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Subscriptions, including those dropped but not cleaned up yet.
+    #[cfg(debug_assertions)]
+    pub(crate) fn subscriptions(&self) -> usize {
+        self.subscriptions.lock().unwrap().len()
+    }
+
+    /// Playing IDs of subscribed events waiting for their end of event.
+    #[cfg(debug_assertions)]
+    pub(crate) fn tracked_playing_ids(&self) -> usize {
+        self.subscribed_playing_ids.lock().unwrap().len()
     }
 }
 
-fn error_handler(In(result): In<Result<(), AkResult>>) {
+fn error_handler(In(result): In<Result<(), AkResult>>, version: Option<Res<RrVersion>>) {
     if let Err(akr) = result {
-        error!("Unexpected Wwise error: {}", akr);
+        match version {
+            Some(version) => error!("Unexpected Wwise error: {} [{}]", akr, *version),
+            None => error!("Unexpected Wwise error: {}", akr),
+        }
     }
 }
 
+const ALLOW_SYNC_RENDER: bool = true;
+
+/// Longest delay between two attempts at restarting the sound engine; see
+/// [RrRenderErrorStrategy::Restart].
+const MAX_RESTART_DELAY: f64 = 30.;
+
+/// State of [audio_rendering()] while rendering fails.
+#[derive(Default)]
+struct RenderFailure {
+    /// Error rendering first failed with; `None` while rendering works.
+    error: Option<AkResult>,
+
+    /// Restarts attempted since rendering started failing.
+    restarts: u32,
+
+    /// Time before which no restart is attempted.
+    next_restart: f64,
+
+    /// Whether the last restart failed, leaving the sound engine uninitialized.
+    engine_down: bool,
+}
+
 // This system must be called late enough to maximize the chances to catch the AppExit event.
 // See https://docs.rs/bevy/latest/bevy/app/struct.AppExit.html
-fn audio_rendering(exits: EventReader<AppExit>) -> Result<(), AkResult> {
-    if !sound_engine::is_initialized() {
-        Ok(())
-    } else if !exits.is_empty() {
-        term_sound_engine()
-    } else {
-        const ALLOW_SYNC_RENDER: bool = true;
-        sound_engine::render_audio(ALLOW_SYNC_RENDER)
+fn audio_rendering(
+    exits: EventReader<AppExit>,
+    plugin_settings: Res<PluginSettingsResource>,
+    mut engine: ResMut<RrEngine>,
+    windows: Res<Windows>,
+    time: Res<Time>,
+    mut device_lost: EventWriter<RrAudioDeviceLost>,
+    mut failure: Local<RenderFailure>,
+) -> Result<(), AkResult> {
+    if !exits.is_empty() && plugin_settings.read().unwrap().plugin.terminate_on_app_exit {
+        *failure = default();
+        return if sound_engine::is_initialized() {
+            engine.terminate()
+        } else {
+            Ok(())
+        };
+    }
+
+    let strategy = plugin_settings.read().unwrap().plugin.render_error_strategy;
+    if !failure.engine_down {
+        if !sound_engine::is_initialized() {
+            return Ok(());
+        }
+
+        let Err(akr) = sound_engine::render_audio(ALLOW_SYNC_RENDER) else {
+            if failure.error.is_some() {
+                info!("Audio rendering works again");
+                *failure = default();
+            }
+            return Ok(());
+        };
+
+        if failure.error.is_none() {
+            failure.error = Some(akr);
+            if strategy == RrRenderErrorStrategy::LogOnly {
+                device_lost.send(RrAudioDeviceLost {
+                    error: akr,
+                    restarted: false,
+                    dummy_output: false,
+                });
+                // Logged once by the error handler, until rendering works again
+                return Err(akr);
+            }
+        }
+    }
+
+    let RrRenderErrorStrategy::Restart {
+        fallback_to_dummy_output,
+    } = strategy
+    else {
+        return Ok(());
+    };
+
+    let now = time.elapsed_seconds_f64();
+    if now < failure.next_restart {
+        return Ok(());
+    }
+
+    let error = failure.error.unwrap_or(AkResult::AK_Fail);
+    if failure.restarts == 0 {
+        error!(
+            "Couldn't render audio - {}; restarting the sound engine",
+            error
+        );
+    }
+    failure.restarts += 1;
+    failure.next_restart = now + 2_f64.powi(failure.restarts as i32).min(MAX_RESTART_DELAY);
+
+    let mut settings = plugin_settings.write().unwrap();
+    let mut dummy_output = false;
+    let mut result = restart_sound_engine(&mut settings, &windows, false);
+    if result.is_err() && fallback_to_dummy_output {
+        warn!("Restarting the sound engine failed; falling back to dummy output");
+        dummy_output = true;
+        result = restart_sound_engine(&mut settings, &windows, true);
+    }
+    failure.engine_down = result.is_err();
+
+    match result {
+        Ok(()) => info!("Sound engine restarted"),
+        Err(akr) => error!(
+            "Couldn't restart the sound engine - {}; retrying in {}s",
+            akr,
+            failure.next_restart - now
+        ),
+    }
+
+    // Sent for the first attempt, then only once a restart succeeds
+    if failure.restarts == 1 || result.is_ok() {
+        device_lost.send(RrAudioDeviceLost {
+            error,
+            restarted: result.is_ok(),
+            dummy_output,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(not(wwrelease))]
+/// Updates [RrCommPorts] after sound engine restarts, which might pick other free ports.
+fn refresh_comm_ports(
+    mut commands: Commands,
+    plugin_settings: Res<PluginSettingsResource>,
+    mut last_generation: Local<Option<u32>>,
+) {
+    let generation = sound_engine_generation();
+    let restarted = matches!(*last_generation, Some(g) if g != generation);
+    *last_generation = Some(generation);
+    if !restarted {
+        return;
+    }
+
+    let settings = plugin_settings.read().unwrap();
+    commands.insert_resource(RrCommPorts {
+        discovery_broadcast: settings.comms.ports.uDiscoveryBroadcast,
+        command: settings.comms.ports.uCommand,
+    });
+}
+
+// Global RTPCs are back to their project defaults after sound engine restarts
+fn keep_disabled_engine_muted(engine: Res<RrEngine>, mut last_generation: Local<u32>) {
+    let generation = sound_engine_generation();
+    if *last_generation == generation {
+        return;
+    }
+    *last_generation = generation;
+
+    if !is_enabled() {
+        engine.set_master_fade(0., Duration::ZERO);
     }
 }
 
-fn process_callbacks(callback_channel: Res<CallbackChannel>, mut ew: EventWriter<AkCallbackEvent>) {
-    while let Ok(cb_info) = callback_channel.receiver.try_recv() {
+/// Wwise states and global RTPC values, to carry them over a sound engine restart.
+///
+/// The state groups and RTPCs are those listed in the soundbanks metadata.
+#[derive(Default)]
+struct GameSyncsSnapshot {
+    states: Vec<(AkUniqueID, AkStateID)>,
+    rtpcs: Vec<(AkUniqueID, AkRtpcValue)>,
+}
+
+impl GameSyncsSnapshot {
+    fn capture(settings: &PluginSettingsInternal) -> Self {
+        let mut snapshot = Self::default();
+        if !sound_engine::is_initialized() {
+            return snapshot;
+        }
+        let Some(metadata) =
+            read_soundbanks_metadata(&gen_banks_folder(settings).join("SoundbanksInfo.json"))
+        else {
+            return snapshot;
+        };
+
+        let info = RrSoundbanksInfo::from_metadata(&metadata);
+        for group in info.state_group_ids() {
+            if let Ok(state) = query_params::get_state(AkID::ID(group)) {
+                snapshot.states.push((group, state));
+            }
+        }
+
+        for rtpc in info.game_parameter_ids() {
+            // Only RTPCs set globally report a global value
+            if let Ok(query_params::RtpcValueType::Global(value)) = query_params::get_rtpc_value(
+                AkID::ID(rtpc),
+                None,
+                None,
+                query_params::RtpcValueType::Global(0.),
+            ) {
+                snapshot.rtpcs.push((rtpc, value));
+            }
+        }
+
+        snapshot
+    }
+
+    fn restore(&self) {
+        for &(group, state) in self.states.iter() {
+            if let Err(akr) = game_syncs::set_state(AkID::ID(group), AkID::ID(state)) {
+                error!(
+                    "Couldn't restore state group {} to {} - {}",
+                    group, state, akr
+                );
+            }
+        }
+        for &(rtpc, value) in self.rtpcs.iter() {
+            if let Err(akr) = game_syncs::SetRtpcValue::new(AkID::ID(rtpc), value).set() {
+                error!("Couldn't restore RTPC {} to {} - {}", rtpc, value, akr);
+            }
+        }
+        debug!(
+            "Restored {} states and {} global RTPCs",
+            self.states.len(),
+            self.rtpcs.len()
+        );
+    }
+}
+
+fn restart_sound_engine(
+    settings: &mut PluginSettingsInternal,
+    windows: &Windows,
+    force_dummy_output: bool,
+) -> Result<(), AkResult> {
+    let game_syncs = GameSyncsSnapshot::capture(settings);
+    term_sound_engine()?;
+    init_sound_engine_internal(settings, windows, force_dummy_output)?;
+    crate::sound_engine::load_bank_untracked(&settings.plugin.init_bank)?;
+    INIT_BANK_LOADED.store(true, Ordering::Release);
+    crate::sound_engine::reload_banks()?;
+    game_syncs.restore();
+    Ok(())
+}
+
+#[cfg(not(wwrelease))]
+#[derive(Default)]
+struct BanksWatch {
+    next_poll: f64,
+    modified: Option<HashMap<PathBuf, SystemTime>>,
+    changed: Vec<PathBuf>,
+}
+
+#[cfg(not(wwrelease))]
+/// Modification times of the bank files in `folder` and its subfolders (localized banks).
+fn bank_files(folder: &Path, files: &mut HashMap<PathBuf, SystemTime>) {
+    let Ok(entries) = std::fs::read_dir(folder) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            bank_files(&path, files);
+        } else if matches!(path.extension(), Some(ext) if ext == "bnk") {
+            if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+                files.insert(path, modified);
+            }
+        }
+    }
+}
+
+#[cfg(not(wwrelease))]
+fn hot_reload_banks(
+    plugin_settings: Res<PluginSettingsResource>,
+    windows: Res<Windows>,
+    time: Res<Time>,
+    mut reloaded: EventWriter<RrBanksReloaded>,
+    mut watch: Local<BanksWatch>,
+) -> Result<(), AkResult> {
+    let now = time.elapsed_seconds_f64();
+    if now < watch.next_poll || !sound_engine::is_initialized() {
+        return Ok(());
+    }
+    watch.next_poll = now + 1.;
+
+    let mut files = HashMap::default();
+    bank_files(
+        &gen_banks_folder(&plugin_settings.read().unwrap()),
+        &mut files,
+    );
+
+    let watch = &mut *watch;
+    let Some(known) = watch.modified.replace(files) else {
+        return Ok(());
+    };
+
+    let mut settled = true;
+    for (path, modified) in watch.modified.as_ref().unwrap() {
+        if known.get(path) != Some(modified) {
+            settled = false;
+            if !watch.changed.contains(path) {
+                watch.changed.push(path.clone());
+            }
+        }
+    }
+
+    // Wait for Wwise to be done writing all the banks
+    if !settled || watch.changed.is_empty() {
+        return Ok(());
+    }
+
+    let changed = std::mem::take(&mut watch.changed);
+    info!("{} soundbanks changed; reloading them", changed.len());
+    restart_sound_engine(&mut plugin_settings.write().unwrap(), &windows, false)?;
+    reloaded.send(RrBanksReloaded { changed });
+    Ok(())
+}
+
+fn track_audio_focus(
+    focus_window: Res<RrAudioFocusWindow>,
+    windows: Res<Windows>,
+    mut window_focused: EventReader<WindowFocused>,
+    mut focus_changed: EventWriter<RrAudioFocusChanged>,
+) {
+    if focus_window.is_changed() && !focus_window.is_added() {
+        window_focused.clear();
+        let focused = matches!(windows.get(focus_window.0), Some(window) if window.is_focused());
+        focus_changed.send(RrAudioFocusChanged { focused });
+        return;
+    }
+
+    if let Some(event) = window_focused
+        .iter()
+        .rfind(|event| event.id == focus_window.0)
+    {
+        focus_changed.send(RrAudioFocusChanged {
+            focused: event.focused,
+        });
+    }
+}
+
+fn process_callbacks(
+    mut commands: Commands,
+    callback_channel: Res<CallbackChannel>,
+    mut ew: EventWriter<AkCallbackEvent>,
+    mut user_cues: EventWriter<RrMusicUserCue>,
+    mut history: ResMut<RrCallbackHistory>,
+) {
+    while let Some(cb_info) = callback_channel.queue.pop() {
+        history.push(&cb_info);
+        let cb_event = AkCallbackEvent(cb_info);
+        if let Some(playing_id) = cb_event.playing_id() {
+            // Not locked while the closure runs, so that it can post events with closures too
+            let closure = callback_channel
+                .closures
+                .lock()
+                .unwrap()
+                .remove(&playing_id);
+            if let Some(mut closure) = closure {
+                closure(&mut commands, &cb_event);
+                if cb_event.callback_type() != AkCallbackType::AK_EndOfEvent {
+                    callback_channel
+                        .closures
+                        .lock()
+                        .unwrap()
+                        .insert(playing_id, closure);
+                }
+            }
+        }
+        callback_channel.dispatch(&cb_event);
+        let cb_info = cb_event.0;
+
+        if let AkCallbackInfo::MusicSync {
+            game_obj_id,
+            playing_id,
+            music_sync_type: AkCallbackType::AK_MusicSyncUserCue,
+            ref user_cue_name,
+            ..
+        } = cb_info
+        {
+            user_cues.send(RrMusicUserCue {
+                cue: user_cue_name.clone(),
+                game_obj_id,
+                playing_id,
+            });
+        }
+
         ew.send(AkCallbackEvent(cb_info));
     }
 }
@@ -311,15 +1587,53 @@ fn process_callbacks(callback_channel: Res<CallbackChannel>, mut ew: EventWriter
 fn setup_audio(
     mut commands: Commands,
     settings: Res<PluginSettingsResource>,
+    mut engine: ResMut<RrEngine>,
+    warm_start_settings: Option<Res<RrWarmStart>>,
 ) -> Result<(), AkResult> {
+    let settings = settings.read().unwrap();
+
     // Load Init.bnk - always required!
-    if let Err(akr) = sound_engine::load_bank_by_name("Init.bnk") {
-        error!("Init.bnk could not be loaded; there will be no audio. Make sure you generate all soundbanks before running");
+    if let Err(akr) = crate::sound_engine::load_bank_untracked(&settings.plugin.init_bank) {
+        error!(
+            "{} could not be loaded; there will be no audio. Make sure you generate all soundbanks \
+            before running, with a Wwise version producing soundbank v{}",
+            settings.plugin.init_bank, AK_SOUNDBANK_VERSION
+        );
         return Err(akr);
     }
+    INIT_BANK_LOADED.store(true, Ordering::Release);
+
+    let metadata =
+        read_soundbanks_metadata(&gen_banks_folder(&settings).join("SoundbanksInfo.json"))
+            .unwrap_or_default();
+    let soundbanks_info = RrSoundbanksInfo::from_metadata(&metadata);
+    commands.insert_resource(RrBusHierarchy::from_info(&soundbanks_info));
+    #[cfg(not(wwrelease))]
+    commands.insert_resource(crate::switch_audit::RrSwitchGroups::from_info(
+        &soundbanks_info,
+    ));
+    if let Some(warm_start_settings) = warm_start_settings {
+        warm_start(&warm_start_settings, &soundbanks_info);
+    }
+    commands.insert_resource(soundbanks_info);
+
+    if let Some(fade_in) = settings.plugin.startup_fade_in {
+        let rtpc = settings.plugin.master_fade_rtpc;
+        game_syncs::SetRtpcValue::new(rtpc, 0.).set()?;
+        engine.fade_in_end = Some(Instant::now() + fade_in);
+
+        // Otherwise, it resumes once audio gets enabled
+        if is_enabled() {
+            game_syncs::SetRtpcValue::new(rtpc, 100.)
+                .with_interp_millis(fade_in.as_millis() as AkTimeMs)
+                .should_bypass_designer_interp(true)
+                .set()?;
+            debug!("Fading in over {:?} with RTPC '{}'", fade_in, rtpc);
+        }
+    }
 
     // Setup default listener
-    if settings.read().unwrap().plugin.spawn_default_listener {
+    if settings.plugin.spawn_default_listener {
         let mut entity_cmds = commands.spawn(RrListenerBundle::default());
         #[cfg(not(wwrelease))]
         entity_cmds.insert(Name::new("RrMainDefaultListener"));
@@ -328,32 +1642,84 @@ fn setup_audio(
     Ok(())
 }
 
-#[cfg_attr(target_os = "linux", allow(unused_variables))]
 #[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "debug", skip_all)]
 fn init_sound_engine(
+    #[cfg_attr(wwrelease, allow(unused_mut, unused_variables))] mut commands: Commands,
     plugin_settings: ResMut<PluginSettingsResource>,
     windows: Res<Windows>,
+    init_language: Option<Res<RrInitLanguage>>,
 ) -> Result<(), AkResult> {
     let mut settings = plugin_settings.write().unwrap();
 
+    // Kept in the settings, so that it's still used on restarts
+    let language_from_env = std::env::var("RRISE_LANGUAGE").ok();
+    if let Some(language) = language_from_env.filter(|language| !language.is_empty()) {
+        settings.plugin.init_language = language;
+    } else if let Some(language) = init_language {
+        settings.plugin.init_language = language.0.clone();
+    }
+
+    init_sound_engine_internal(&mut settings, &windows, false)?;
+
+    #[cfg(not(wwrelease))]
+    commands.insert_resource(RrCommPorts {
+        discovery_broadcast: settings.comms.ports.uDiscoveryBroadcast,
+        command: settings.comms.ports.uCommand,
+    });
+
+    Ok(())
+}
+
+#[cfg(not(wwrelease))]
+/// Replaces the ports of `comms` that are already taken by free ones.
+fn pick_free_comm_ports(comms: &mut AkCommSettings) {
+    use std::net::{TcpListener, UdpSocket};
+
+    let discovery = comms.ports.uDiscoveryBroadcast;
+    if UdpSocket::bind(("0.0.0.0", discovery)).is_err() {
+        if let Some(port) = UdpSocket::bind(("0.0.0.0", 0))
+            .and_then(|socket| socket.local_addr())
+            .ok()
+            .map(|addr| addr.port())
+        {
+            warn!(
+                "Profiler discovery port {} is taken; using {} instead (connect to this game by IP)",
+                discovery, port
+            );
+            comms.ports.uDiscoveryBroadcast = port;
+        }
+    }
+
+    let command = comms.ports.uCommand;
+    if TcpListener::bind(("0.0.0.0", command)).is_err() {
+        if let Some(port) = TcpListener::bind(("0.0.0.0", 0))
+            .and_then(|listener| listener.local_addr())
+            .ok()
+            .map(|addr| addr.port())
+        {
+            warn!(
+                "Profiler command port {} is taken; using {} instead",
+                command, port
+            );
+            comms.ports.uCommand = port;
+        }
+    }
+}
+
+#[cfg_attr(target_os = "linux", allow(unused_variables))]
+fn init_sound_engine_internal(
+    settings: &mut PluginSettingsInternal,
+    windows: &Windows,
+    force_dummy_output: bool,
+) -> Result<(), AkResult> {
     // init memorymgr
     memory_mgr::init(&mut settings.mem)?;
     assert!(memory_mgr::is_initialized());
     debug!("Memory manager initialized");
 
     // init streamingmgr
-    #[cfg(target_os = "windows")]
-    let platform = "Windows";
-    #[cfg(target_os = "linux")]
-    let platform = "Linux";
-    let mut gen_banks_folder = settings.plugin.banks_location.join(platform);
-    if gen_banks_folder.is_relative() {
-        gen_banks_folder = FileAssetIo::get_base_path()
-            .join(&settings.bevy_asset_folder)
-            .join(gen_banks_folder);
-    }
-
+    let gen_banks_folder = gen_banks_folder(settings);
     debug!("Banks will be discovered from: {:?}", gen_banks_folder);
 
     stream_mgr::init_default_stream_mgr(
@@ -364,7 +1730,7 @@ fn init_sound_engine(
     debug!("Default streaming manager initialized");
 
     stream_mgr::set_current_language(&settings.plugin.init_language)?;
-    debug!("Current language set");
+    debug!("Current language set to {}", settings.plugin.init_language);
 
     // init soundengine
 
@@ -385,10 +1751,38 @@ fn init_sound_engine(
         );
     }
 
-    sound_engine::init(
+    let main_output_shareset = settings
+        .engine
+        .get_mut()
+        .settings_main_output
+        .audioDeviceShareset;
+    let dummy_output_from_env = matches!(std::env::var("RRISE_DUMMY_OUTPUT"), Ok(v) if v != "0");
+    if force_dummy_output || settings.plugin.use_dummy_output || dummy_output_from_env {
+        settings
+            .engine
+            .get_mut()
+            .settings_main_output
+            .audioDeviceShareset = crate::sound_engine::get_id_from_string(DUMMY_OUTPUT_SHARESET);
+        debug!("Audio will be rendered to {}", DUMMY_OUTPUT_SHARESET);
+    }
+
+    if let Some(path) = settings.plugin.plugin_dll_path.clone() {
+        let engine = std::mem::take(settings.engine.get_mut());
+        *settings.engine.get_mut() = engine.with_plugin_dll_path(path.to_string_lossy());
+        debug!("Plugins will be discovered from: {:?}", path);
+    }
+
+    let result = sound_engine::init(
         &mut settings.engine.borrow_mut(),
         &mut settings.pltfm.borrow_mut(),
-    )?;
+    );
+    // Only for this init, so that later restarts try the actual output device again
+    settings
+        .engine
+        .get_mut()
+        .settings_main_output
+        .audioDeviceShareset = main_output_shareset;
+    result?;
     debug!("Internal sound engine initialized");
 
     // init musicengine
@@ -398,23 +1792,52 @@ fn init_sound_engine(
     // init comms
     #[cfg(not(wwrelease))]
     {
+        if settings.plugin.auto_pick_comm_ports {
+            pick_free_comm_ports(&mut settings.comms);
+        }
         communication::init(&settings.comms)?;
-        debug!("Profiling (comms) initialized");
+        info!(
+            "Profiling (comms) initialized; discovery port {}, command port {}",
+            settings.comms.ports.uDiscoveryBroadcast, settings.comms.ports.uCommand
+        );
     }
 
     if !sound_engine::is_initialized() {
         error!("Unknown error: the sound engine didn't initialize properly");
         Err(AkResult::AK_Fail)
     } else {
+        SOUND_ENGINE_GENERATION.fetch_add(1, Ordering::AcqRel);
         Ok(())
     }
 }
 
+/// Folder of the soundbanks generated for the current platform.
+fn gen_banks_folder(settings: &PluginSettingsInternal) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    let platform = "Windows";
+    #[cfg(target_os = "linux")]
+    let platform = "Linux";
+    let gen_banks_folder = match &settings.plugin.platform_folder {
+        Some(platform_folder) => settings.plugin.banks_location.join(platform_folder),
+        None => settings.plugin.banks_location.join(platform),
+    };
+    if gen_banks_folder.is_relative() {
+        FileAssetIo::get_base_path()
+            .join(&settings.bevy_asset_folder)
+            .join(gen_banks_folder)
+    } else {
+        gen_banks_folder
+    }
+}
+
 #[tracing::instrument(level = "debug", skip_all)]
 fn term_sound_engine() -> Result<(), AkResult> {
-    sound_engine::stop_all(None);
-    sound_engine::unregister_all_game_obj()?;
-    debug!("All objects stopped and unregistered");
+    // Might be partially initialized when retrying a failed restart
+    if sound_engine::is_initialized() {
+        sound_engine::stop_all(None);
+        sound_engine::unregister_all_game_obj()?;
+        debug!("All objects stopped and unregistered");
+    }
 
     // term comms
     #[cfg(not(wwrelease))]
@@ -431,6 +1854,7 @@ fn term_sound_engine() -> Result<(), AkResult> {
 
     // term soundengine
     sound_engine::term();
+    INIT_BANK_LOADED.store(false, Ordering::Release);
     debug!("Internal sound engine terminated");
 
     // term streamingmgr
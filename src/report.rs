@@ -0,0 +1,192 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::{RrEmitter, RrListener, RrObjDesc, RrRegistered};
+use crate::plugin::{RrEngine, RrVersion};
+use crate::voice_queue::RrVoiceQueue;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rrise::{sound_engine, AkCallbackInfo};
+use std::collections::VecDeque;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Latest report, printed if the app panics; see [RriseBasicSettings::dump_state_on_panic].
+///
+/// [RriseBasicSettings::dump_state_on_panic]: crate::plugin::RriseBasicSettings::dump_state_on_panic
+static LAST_REPORT: Mutex<String> = Mutex::new(String::new());
+
+#[derive(Debug, Default, Resource)]
+/// Last [Self::CAPACITY] callbacks received in this world, most recent last.
+pub struct RrCallbackHistory(VecDeque<AkCallbackInfo>);
+
+impl RrCallbackHistory {
+    pub const CAPACITY: usize = 100;
+
+    pub(crate) fn push(&mut self, cb_info: &AkCallbackInfo) {
+        if self.0.len() == Self::CAPACITY {
+            self.0.pop_front();
+        }
+        self.0.push_back(cb_info.clone());
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &AkCallbackInfo> {
+        self.0.iter()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Send this event to log an [RrAudioState] report at the `info` level.
+pub struct RrDumpAudioState;
+
+#[derive(SystemParam)]
+/// Human-readable report of the audio state of the world, to attach to bug reports.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn on_f12(keys: Res<Input<KeyCode>>, state: RrAudioState) {
+///     if keys.just_pressed(KeyCode::F12) {
+///         std::fs::write("audio_report.txt", state.report()).unwrap();
+///     }
+/// }
+/// ```
+pub struct RrAudioState<'w, 's> {
+    version: Option<Res<'w, RrVersion>>,
+    engine: Option<Res<'w, RrEngine>>,
+    listeners: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static RrListener,
+            &'static GlobalTransform,
+            Option<&'static RrRegistered>,
+        ),
+    >,
+    emitters: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static RrEmitter,
+            &'static GlobalTransform,
+            Option<&'static RrRegistered>,
+        ),
+    >,
+    voice_queue: Res<'w, RrVoiceQueue>,
+    history: Res<'w, RrCallbackHistory>,
+}
+
+impl<'w, 's> RrAudioState<'w, 's> {
+    pub fn report(&self) -> String {
+        let mut r = String::new();
+
+        // Writing to a String never fails
+        let _ = writeln!(r, "===== bevy-rrise audio state =====");
+        if let Some(version) = &self.version {
+            let _ = writeln!(r, "{}", **version);
+        }
+        let _ = writeln!(
+            r,
+            "Sound engine initialized: {}, audio enabled: {}",
+            sound_engine::is_initialized(),
+            matches!(&self.engine, Some(engine) if engine.is_enabled())
+        );
+
+        let _ = writeln!(r, "\n-- Banks");
+        let _ = writeln!(
+            r,
+            "Init.bnk: {}",
+            if crate::plugin::is_init_bank_loaded() {
+                "loaded"
+            } else {
+                "NOT LOADED"
+            }
+        );
+        for bank in crate::sound_engine::loaded_banks() {
+            let _ = writeln!(r, "{}: loaded", bank);
+        }
+        let _ = writeln!(r, "(banks loaded with rrise directly aren't tracked)");
+
+        let _ = writeln!(r, "\n-- Buses");
+        let _ = writeln!(r, "(bus volumes aren't exposed by Rrise)");
+
+        let _ = writeln!(r, "\n-- Listeners ({})", self.listeners.iter().len());
+        for (e, rr, tfm, registered) in self.listeners.iter() {
+            let _ = writeln!(
+                r,
+                "{}{}{} at {}",
+                RrObjDesc::new(Some(e), rr.name.as_deref()),
+                if rr.is_default() { " [default]" } else { "" },
+                if registered.is_some() {
+                    ""
+                } else {
+                    " [unregistered]"
+                },
+                tfm.translation()
+            );
+        }
+
+        let _ = writeln!(r, "\n-- Emitters ({})", self.emitters.iter().len());
+        for (e, rr, tfm, registered) in self.emitters.iter() {
+            let _ = writeln!(
+                r,
+                "{}{} at {}, event '{}', playing {:?}",
+                RrObjDesc::new(Some(e), rr.name.as_deref()),
+                if registered.is_some() {
+                    ""
+                } else {
+                    " [unregistered]"
+                },
+                tfm.translation(),
+                rr.event_id,
+                rr.playing_events()
+                    .iter()
+                    .map(|playing| playing.playing_id())
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        let _ = writeln!(r, "\n-- Voice queue");
+        let _ = writeln!(r, "{} lines", self.voice_queue.len());
+
+        let _ = writeln!(r, "\n-- Last {} callbacks", self.history.0.len());
+        for cb_info in self.history.iter() {
+            let _ = writeln!(r, "{:?}", cb_info);
+        }
+
+        r
+    }
+}
+
+pub(crate) fn dump_audio_state(mut requests: EventReader<RrDumpAudioState>, state: RrAudioState) {
+    if requests.iter().count() > 0 {
+        info!("{}", state.report());
+    }
+}
+
+/// Keeps the report printed on panic reasonably fresh.
+pub(crate) fn snapshot_audio_state(state: RrAudioState, time: Res<Time>, mut next: Local<f64>) {
+    let now = time.elapsed_seconds_f64();
+    if now < *next {
+        return;
+    }
+    *next = now + 1.;
+
+    let report = state.report();
+    *LAST_REPORT.lock().unwrap() = format!("(as of {:.1}s after startup)\n{}", now, report);
+}
+
+pub(crate) fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Never panic again while panicking
+        if let Ok(report) = LAST_REPORT.try_lock() {
+            if !report.is_empty() {
+                eprintln!("{}", *report);
+            }
+        }
+        previous(info);
+    }));
+}
@@ -0,0 +1,225 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::count_set_rtpc;
+use crate::emitter_listener::{ak_id_of, unique_id, RrEmitter, RrObjDesc, RrRegistered};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rrise::game_syncs::SetRtpcValue;
+use rrise::{AkGameObjectID, AkID, AkResult, AkRtpcValue};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Game objects an RTPC value is set on with [RrRtpcs::set()].
+pub enum RrRtpcTarget {
+    /// The RTPC is set globally.
+    Global,
+
+    /// The game object of this entity.
+    Entity(Entity),
+
+    /// The registered emitters among the descendants of this entity, not including itself.
+    ///
+    /// Useful for objects made of several emitters, like an engine and its exhausts.
+    Descendants(Entity),
+}
+
+impl RrRtpcTarget {
+    pub fn entity(entity: Entity) -> Self {
+        Self::Entity(entity)
+    }
+
+    pub fn descendants(entity: Entity) -> Self {
+        Self::Descendants(entity)
+    }
+}
+
+#[derive(SystemParam)]
+/// Sets RTPC values on the game objects described by an [RrRtpcTarget].
+///
+/// ### Example
+/// ```rust,ignore
+/// fn update_doppler(drones: Query<Entity, With<Drone>>, rtpcs: RrRtpcs) -> Result<(), AkResult> {
+///     for drone in drones.iter() {
+///         rtpcs.set("Doppler", 1.2, RrRtpcTarget::descendants(drone))?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct RrRtpcs<'w, 's> {
+    children: Query<'w, 's, &'static Children>,
+    emitters: Query<'w, 's, (), (With<RrEmitter>, With<RrRegistered>)>,
+}
+
+impl<'w, 's> RrRtpcs<'w, 's> {
+    /// Game objects targeted by `target`; empty for [RrRtpcTarget::Global].
+    pub fn resolve(&self, target: RrRtpcTarget) -> Vec<AkGameObjectID> {
+        match target {
+            RrRtpcTarget::Global => vec![],
+            RrRtpcTarget::Entity(e) => vec![ak_id_of(e)],
+            RrRtpcTarget::Descendants(root) => {
+                let mut ids = vec![];
+                let mut to_visit = vec![root];
+                while let Some(e) = to_visit.pop() {
+                    for &child in self.children.get(e).into_iter().flatten() {
+                        if self.emitters.contains(child) {
+                            ids.push(ak_id_of(child));
+                        }
+                        to_visit.push(child);
+                    }
+                }
+                ids
+            }
+        }
+    }
+
+    /// Sets `value` for `rtpc` on all the game objects of `target`.
+    ///
+    /// Stops at the first failure.
+    pub fn set<T: Into<AkID<'static>>>(
+        &self,
+        rtpc: T,
+        value: AkRtpcValue,
+        target: RrRtpcTarget,
+    ) -> Result<(), AkResult> {
+        let rtpc = rtpc.into();
+
+        if target == RrRtpcTarget::Global {
+            count_set_rtpc();
+            return SetRtpcValue::new(rtpc, value).set();
+        }
+
+        for id in self.resolve(target) {
+            count_set_rtpc();
+            if let Err(akr) = SetRtpcValue::new(rtpc, value).for_target(id).set() {
+                error!(
+                    "Couldn't set RTPC '{}' on game object {} - {}",
+                    rtpc, id, akr
+                );
+                return Err(akr);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default, Component)]
+/// RTPC values of the game object of its entity (an emitter or a listener), set in Wwise whenever
+/// they change.
+///
+/// Values are set at the end of the frame, and again whenever the game object gets (re)registered.
+/// For values needed by the auto post of an emitter, use its
+/// [`rtpc_presets`](RrEmitter::rtpc_presets) instead.
+///
+/// ### Example
+/// ```rust,ignore
+/// commands.spawn((
+///     RrEmitterBundle::new(Vec3::ZERO).with_event("Play_Engine", false),
+///     RrRtpc::default().with("Speed", 0.),
+///     Car::default(),
+/// ));
+///
+/// fn update_engines(mut cars: Query<(&Car, &mut RrRtpc)>) {
+///     for (car, mut rtpc) in cars.iter_mut() {
+///         rtpc.set("Speed", car.speed);
+///     }
+/// }
+/// ```
+pub struct RrRtpc {
+    values: Vec<RrRtpcValue>,
+}
+
+#[derive(Debug, Clone)]
+struct RrRtpcValue {
+    rtpc: AkID<'static>,
+    value: AkRtpcValue,
+    synced: bool,
+}
+
+impl RrRtpc {
+    pub fn with<T: Into<AkID<'static>>>(mut self, rtpc: T, value: AkRtpcValue) -> Self {
+        self.set(rtpc, value);
+        self
+    }
+
+    /// Sets `value` for `rtpc`; it is only sent to Wwise if it changed.
+    ///
+    /// RTPCs are told apart by ID, so a name and its ID refer to the same RTPC.
+    pub fn set<T: Into<AkID<'static>>>(&mut self, rtpc: T, value: AkRtpcValue) {
+        let rtpc = rtpc.into();
+        let id = unique_id(rtpc);
+        match self.values.iter_mut().find(|v| unique_id(v.rtpc) == id) {
+            Some(v) if v.value == value => {}
+            Some(v) => {
+                v.value = value;
+                v.synced = false;
+            }
+            None => self.values.push(RrRtpcValue {
+                rtpc,
+                value,
+                synced: false,
+            }),
+        }
+    }
+
+    /// Value set for `rtpc`, if any.
+    pub fn get<T: Into<AkID<'static>>>(&self, rtpc: T) -> Option<AkRtpcValue> {
+        let id = unique_id(rtpc.into());
+        self.values
+            .iter()
+            .find(|v| unique_id(v.rtpc) == id)
+            .map(|v| v.value)
+    }
+
+    /// All `(rtpc, value)` pairs set.
+    pub fn iter(&self) -> impl Iterator<Item = (AkID<'static>, AkRtpcValue)> + '_ {
+        self.values.iter().map(|v| (v.rtpc, v.value))
+    }
+}
+
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn sync_rtpc_components(
+    mut query: Query<
+        (
+            Entity,
+            &mut RrRtpc,
+            ChangeTrackers<RrRtpc>,
+            ChangeTrackers<RrRegistered>,
+        ),
+        With<RrRegistered>,
+    >,
+    mut last_generation: Local<Option<u32>>,
+) {
+    // Game objects got registered again, without their RTPC values
+    let generation = crate::plugin::sound_engine_generation();
+    let restarted = matches!(*last_generation, Some(g) if g != generation);
+    *last_generation = Some(generation);
+
+    for (e, mut rr_rtpc, rtpc_tracker, registered) in query.iter_mut() {
+        let resync = restarted || registered.is_added();
+        if !resync && !rtpc_tracker.is_changed() {
+            continue;
+        }
+
+        let id = ak_id_of(e);
+        // Don't trigger change detection again
+        for v in rr_rtpc.bypass_change_detection().values.iter_mut() {
+            if v.synced && !resync {
+                continue;
+            }
+            v.synced = true;
+
+            count_set_rtpc();
+            if let Err(akr) = SetRtpcValue::new(v.rtpc, v.value).for_target(id).set() {
+                error!(
+                    "Couldn't set RTPC '{}' on {} - {}",
+                    v.rtpc,
+                    RrObjDesc::new(Some(e), None),
+                    akr
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,321 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::{ak_id_of, RrEmitter, RrObjDesc, RrRegistered};
+use crate::plugin::CallbackChannel;
+use bevy::prelude::*;
+use rrise::game_syncs::{set_state, set_switch, SetRtpcValue};
+use rrise::{AkID, AkPlayingID, AkRtpcValue, AK_INVALID_PLAYING_ID};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+pub(crate) enum RrSequenceStep {
+    #[cfg_attr(wwrelease, allow(dead_code))]
+    Log(String),
+    Post(AkID<'static>),
+    WaitEnd,
+    Wait(Duration),
+    SetState(AkID<'static>, AkID<'static>),
+    SetSwitch(AkID<'static>, AkID<'static>),
+    SetRtpc(AkID<'static>, AkRtpcValue),
+}
+
+#[derive(Debug, Clone, Default, Component)]
+/// Scripted audio moment, played step by step across frames on the [RrEmitter] of the same
+/// entity.
+///
+/// Steps run as soon as the emitter is registered, until one has to wait. Once all steps are
+/// done, this component gets removed and an [RrSequenceFinished] event is sent.
+///
+/// ### Example
+/// ```rust,ignore
+/// commands.entity(boss).insert(
+///     RrSequence::new()
+///         .post("Play_Boss_Intro")
+///         .wait_end()
+///         .set_state("Phase", "Two")
+///         .post("Play_Boss_Loop"),
+/// );
+/// ```
+pub struct RrSequence {
+    pub(crate) steps: VecDeque<RrSequenceStep>,
+    last_playing_id: AkPlayingID,
+    waiting_since: Option<f64>,
+}
+
+impl RrSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Posts `event` on the emitter, with its callback flags.
+    pub fn post<T: Into<AkID<'static>>>(mut self, event: T) -> Self {
+        self.steps.push_back(RrSequenceStep::Post(event.into()));
+        self
+    }
+
+    /// Waits for the last event posted by this sequence to end.
+    pub fn wait_end(mut self) -> Self {
+        self.steps.push_back(RrSequenceStep::WaitEnd);
+        self
+    }
+
+    pub fn wait(mut self, duration: Duration) -> Self {
+        self.steps.push_back(RrSequenceStep::Wait(duration));
+        self
+    }
+
+    /// Sets a global state.
+    ///
+    /// `group` and `state` should both be names or both be IDs.
+    pub fn set_state<T: Into<AkID<'static>>>(mut self, group: T, state: T) -> Self {
+        self.steps
+            .push_back(RrSequenceStep::SetState(group.into(), state.into()));
+        self
+    }
+
+    /// Sets a switch on the emitter.
+    ///
+    /// `group` and `state` should both be names or both be IDs.
+    pub fn set_switch<T: Into<AkID<'static>>>(mut self, group: T, state: T) -> Self {
+        self.steps
+            .push_back(RrSequenceStep::SetSwitch(group.into(), state.into()));
+        self
+    }
+
+    /// Sets an RTPC value on the emitter.
+    pub fn set_rtpc<T: Into<AkID<'static>>>(mut self, rtpc: T, value: AkRtpcValue) -> Self {
+        self.steps
+            .push_back(RrSequenceStep::SetRtpc(rtpc.into(), value));
+        self
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Sent when an [RrSequence] ran all its steps, or an [RrPlaylist] played all its loops.
+pub struct RrSequenceFinished {
+    pub entity: Entity,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How many times an [RrPlaylist] plays its items.
+pub enum RrPlaylistLoop {
+    Once,
+    Times(u32),
+    Forever,
+}
+
+#[derive(Debug, Clone)]
+struct RrPlaylistItem {
+    event: AkID<'static>,
+    delay: Duration,
+}
+
+#[derive(Debug, Clone, Component)]
+/// Ordered list of events played one after the other on the [RrEmitter] of the same entity, like
+/// a Wwise playlist container authored on the game side.
+///
+/// Each item is posted once the previous one ended, after its own delay. Once all loops are done,
+/// this component gets removed and an [RrSequenceFinished] event is sent; remove it yourself to
+/// stop moving on to the next items.
+///
+/// ### Example
+/// ```rust,ignore
+/// commands.entity(forest).insert(
+///     RrPlaylist::new()
+///         .with_item("Play_Birds", Duration::ZERO)
+///         .with_item("Play_Woodpecker", Duration::from_secs(4))
+///         .with_loop(RrPlaylistLoop::Forever),
+/// );
+/// ```
+pub struct RrPlaylist {
+    items: Vec<RrPlaylistItem>,
+
+    /// Defaults to [RrPlaylistLoop::Once].
+    pub looping: RrPlaylistLoop,
+
+    next: usize,
+    loops_done: u32,
+    last_playing_id: AkPlayingID,
+    waiting_since: Option<f64>,
+}
+
+impl Default for RrPlaylist {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            looping: RrPlaylistLoop::Once,
+            next: 0,
+            loops_done: 0,
+            last_playing_id: AK_INVALID_PLAYING_ID,
+            waiting_since: None,
+        }
+    }
+}
+
+impl RrPlaylist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `event`, posted `delay` after the end of the previous item.
+    pub fn with_item<T: Into<AkID<'static>>>(mut self, event: T, delay: Duration) -> Self {
+        self.items.push(RrPlaylistItem {
+            event: event.into(),
+            delay,
+        });
+        self
+    }
+
+    pub fn with_loop(mut self, looping: RrPlaylistLoop) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    pub fn is_done(&self) -> bool {
+        let loops = match self.looping {
+            RrPlaylistLoop::Once => 1,
+            RrPlaylistLoop::Times(loops) => loops,
+            RrPlaylistLoop::Forever => return self.items.is_empty(),
+        };
+        self.items.is_empty() || self.loops_done >= loops
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn run_sequences(
+    mut commands: Commands,
+    mut sequences: Query<(Entity, &mut RrSequence, &mut RrEmitter), With<RrRegistered>>,
+    cb_channel: Res<CallbackChannel>,
+    time: Res<Time>,
+    mut finished: EventWriter<RrSequenceFinished>,
+) {
+    let now = time.elapsed_seconds_f64();
+
+    for (e, mut sequence, mut rr) in sequences.iter_mut() {
+        let sequence = &mut *sequence;
+        let id = ak_id_of(e);
+
+        while let Some(step) = sequence.steps.front() {
+            match step {
+                RrSequenceStep::Log(message) => {
+                    info!("{}: {}", RrObjDesc::new(Some(e), None), message);
+                }
+                RrSequenceStep::Post(event) => {
+                    let flags = rr.flags;
+                    sequence.last_playing_id =
+                        rr.post_event(*event, flags, Some(cb_channel.clone()));
+                }
+                RrSequenceStep::WaitEnd => {
+                    if sequence.last_playing_id != AK_INVALID_PLAYING_ID
+                        && rr.is_playing_id(sequence.last_playing_id)
+                    {
+                        break;
+                    }
+                }
+                RrSequenceStep::Wait(duration) => {
+                    let since = *sequence.waiting_since.get_or_insert(now);
+                    if now - since < duration.as_secs_f64() {
+                        break;
+                    }
+                    sequence.waiting_since = None;
+                }
+                RrSequenceStep::SetState(group, state) => {
+                    if let Err(akr) = set_state(*group, *state) {
+                        error!("Couldn't set state '{}' to '{}' - {}", group, state, akr);
+                    }
+                }
+                RrSequenceStep::SetSwitch(group, state) => {
+                    if let Err(akr) = set_switch(*group, *state, id) {
+                        error!(
+                            "Couldn't set switch '{}' to '{}' on {} - {}",
+                            group,
+                            state,
+                            RrObjDesc::new(Some(e), None),
+                            akr
+                        );
+                    }
+                }
+                RrSequenceStep::SetRtpc(rtpc, value) => {
+                    if let Err(akr) = SetRtpcValue::new(*rtpc, *value).for_target(id).set() {
+                        error!(
+                            "Couldn't set RTPC '{}' on {} - {}",
+                            rtpc,
+                            RrObjDesc::new(Some(e), None),
+                            akr
+                        );
+                    }
+                }
+            }
+            sequence.steps.pop_front();
+        }
+
+        if sequence.is_done() {
+            commands.entity(e).remove::<RrSequence>();
+            finished.send(RrSequenceFinished { entity: e });
+        }
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn run_playlists(
+    mut commands: Commands,
+    mut playlists: Query<(Entity, &mut RrPlaylist, &mut RrEmitter), With<RrRegistered>>,
+    cb_channel: Res<CallbackChannel>,
+    time: Res<Time>,
+    mut finished: EventWriter<RrSequenceFinished>,
+) {
+    let now = time.elapsed_seconds_f64();
+
+    for (e, mut playlist, mut rr) in playlists.iter_mut() {
+        let playlist = &mut *playlist;
+
+        while !playlist.is_done() {
+            if playlist.last_playing_id != AK_INVALID_PLAYING_ID
+                && rr.is_playing_id(playlist.last_playing_id)
+            {
+                break;
+            }
+
+            let item = &playlist.items[playlist.next];
+            let since = *playlist.waiting_since.get_or_insert(now);
+            if now - since < item.delay.as_secs_f64() {
+                break;
+            }
+            playlist.waiting_since = None;
+
+            let flags = rr.flags;
+            playlist.last_playing_id = rr.post_event(item.event, flags, Some(cb_channel.clone()));
+            debug!(
+                "Playlist item {} '{}' posted on {}",
+                playlist.next,
+                item.event,
+                RrObjDesc::new(Some(e), None)
+            );
+
+            playlist.next += 1;
+            if playlist.next == playlist.items.len() {
+                playlist.next = 0;
+                playlist.loops_done = playlist.loops_done.saturating_add(1);
+            }
+
+            // Keeps a failed post from posting the whole playlist this frame
+            if playlist.last_playing_id == AK_INVALID_PLAYING_ID {
+                break;
+            }
+        }
+
+        let last_playing = playlist.last_playing_id != AK_INVALID_PLAYING_ID
+            && rr.is_playing_id(playlist.last_playing_id);
+        if playlist.is_done() && !last_playing {
+            commands.entity(e).remove::<RrPlaylist>();
+            finished.send(RrSequenceFinished { entity: e });
+        }
+    }
+}
@@ -4,20 +4,581 @@
 
 use bevy::prelude::*;
 use rrise::sound_engine::{
-    register_game_obj, set_position, unregister_game_obj, PostEvent as RPostEvent,
+    load_bank_by_name, register_game_obj, unregister_game_obj, PostEvent as RPostEvent,
 };
 
+use crate::diagnostics::{count_post, set_position};
 use crate::plugin::CallbackChannel;
+use crate::warm_start::record_post;
 use crate::ToAkTransform;
-use rrise::AkTransform;
-pub use rrise::{AkCallbackInfo, AkCallbackType, AkGameObjectID, AkID, AkPlayingID, AkResult};
+use bevy::ecs::schedule::StateData;
+use bevy::tasks::IoTaskPool;
+use rrise::{AkBankID, AkTransform, AK_INVALID_PLAYING_ID};
+pub use rrise::{
+    AkCallbackInfo, AkCallbackType, AkGameObjectID, AkID, AkPlayingID, AkResult, AkUniqueID,
+};
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
 use tracing::{debug, error};
 
+/// Banks loaded with [load_bank()], in loading order.
+static LOADED_BANKS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Bank events waiting to be sent by [send_bank_events()].
+static BANK_EVENTS: Mutex<Vec<RrBankEvent>> = Mutex::new(Vec::new());
+
+type DeferredCall = Box<dyn FnOnce() + Send>;
+
+/// Calls waiting to be made by [run_deferred_calls()].
+static DEFERRED_CALLS: Mutex<Vec<DeferredCall>> = Mutex::new(Vec::new());
+
 pub struct SoundEngine {}
 
+/// Loads the soundbank `name` (like `"TheBank.bnk"`), and remembers it so that it gets loaded
+/// again whenever bevy-rrise restarts the sound engine (audio device lost, soundbanks
+/// hot-reloaded...).
+///
+/// An [RrBankEvent] is sent with the result, on the next frame.
+///
+/// Banks loaded with [rrise::sound_engine::load_bank_by_name()] directly are lost on restarts.
+pub fn load_bank<T: AsRef<str>>(name: T) -> Result<AkBankID, AkResult> {
+    let name = name.as_ref();
+    let bank_id = load_bank_untracked(name)?;
+
+    let mut banks = LOADED_BANKS.lock().unwrap();
+    if !banks.iter().any(|bank| bank == name) {
+        banks.push(name.to_string());
+    }
+
+    Ok(bank_id)
+}
+
+/// Loads `name` like [load_bank()], without remembering it; for the banks bevy-rrise loads by
+/// itself, like Init.bnk.
+pub(crate) fn load_bank_untracked(name: &str) -> Result<AkBankID, AkResult> {
+    let result = load_bank_by_name(name);
+    BANK_EVENTS.lock().unwrap().push(match result {
+        Ok(bank_id) => RrBankEvent::Loaded {
+            name: name.to_string(),
+            bank_id,
+        },
+        Err(result) => RrBankEvent::Failed {
+            name: name.to_string(),
+            result,
+        },
+    });
+    result
+}
+
+/// Makes `call` on the main thread, at the beginning of the next frame once callbacks are
+/// processed.
+///
+/// Wwise callbacks run on a Wwise thread, where calling back into the sound engine can deadlock;
+/// from closures given to [rrise::sound_engine::PostEvent::post_with_callback()], defer your
+/// rrise and bevy-rrise calls with this instead. Closures given to
+/// [RrEmitter::post_with_closure()](crate::emitter_listener::RrEmitter::post_with_closure())
+/// already run on the main thread.
+///
+/// ### Example
+/// ```rust,ignore
+/// PostEvent::new(id, "Play_Intro").post_with_callback(move |cb_info| {
+///     if let AkCallbackInfo::Event { .. } = cb_info {
+///         defer(move || {
+///             let _ = PostEvent::new(id, "Play_Loop").post();
+///         });
+///     }
+/// })?;
+/// ```
+pub fn defer<F: FnOnce() + Send + 'static>(call: F) {
+    DEFERRED_CALLS.lock().unwrap().push(Box::new(call));
+}
+
+/// Calls deferred but not made yet.
+#[cfg(debug_assertions)]
+pub(crate) fn pending_deferred_calls() -> usize {
+    DEFERRED_CALLS.lock().unwrap().len()
+}
+
+/// Bank events not sent yet by [send_bank_events()].
+#[cfg(debug_assertions)]
+pub(crate) fn pending_bank_events() -> usize {
+    BANK_EVENTS.lock().unwrap().len()
+}
+
+pub(crate) fn run_deferred_calls() {
+    // Taken first: calls deferred by these run next frame, and can't deadlock on the queue
+    let calls = std::mem::take(&mut *DEFERRED_CALLS.lock().unwrap());
+    for call in calls {
+        call();
+    }
+}
+
+/// Whether `name` was loaded with [load_bank()].
+pub fn is_bank_loaded(name: &str) -> bool {
+    LOADED_BANKS.lock().unwrap().iter().any(|bank| bank == name)
+}
+
+/// Banks loaded with [load_bank()], in loading order.
+pub(crate) fn loaded_banks() -> Vec<String> {
+    LOADED_BANKS.lock().unwrap().clone()
+}
+
+/// Ties soundbanks to Bevy [State]s.
+pub trait RrAppExt {
+    /// Loads `banks` with [load_bank()] each time `state` is entered, unless they're already
+    /// loaded.
+    ///
+    /// *Remark* Rrise can't unload banks yet, so they stay loaded when `state` is exited.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// app.add_state(GameState::Menu)
+    ///     .load_banks_in_state(GameState::Level1, ["Level1.bnk", "Level1_Music.bnk"]);
+    /// ```
+    fn load_banks_in_state<S, I, T>(&mut self, state: S, banks: I) -> &mut Self
+    where
+        S: StateData,
+        I: IntoIterator<Item = T>,
+        T: Into<String>;
+}
+
+impl RrAppExt for App {
+    fn load_banks_in_state<S, I, T>(&mut self, state: S, banks: I) -> &mut Self
+    where
+        S: StateData,
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let banks: Vec<String> = banks.into_iter().map(Into::into).collect();
+        self.add_system_set(SystemSet::on_enter(state).with_system(move || {
+            for bank in banks.iter().filter(|bank| !is_bank_loaded(bank)) {
+                if let Err(akr) = load_bank(bank) {
+                    error!("Couldn't load {} - {}", bank, akr);
+                }
+            }
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Sent whenever bevy-rrise loads a soundbank: Init.bnk, banks loaded with [load_bank()] (directly,
+/// with [RrBanks], [RrBankLoadQueue]...) and banks loaded again after a sound engine restart.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn on_bank_event(mut bank_events: EventReader<RrBankEvent>, mut state: ResMut<State<AppState>>) {
+///     for event in bank_events.iter() {
+///         if let RrBankEvent::Failed { name, .. } = event {
+///             if name == "Init.bnk" {
+///                 state.set(AppState::NoAudioWarning).unwrap();
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub enum RrBankEvent {
+    Loaded { name: String, bank_id: AkBankID },
+    Failed { name: String, result: AkResult },
+}
+
+impl RrBankEvent {
+    pub fn name(&self) -> &str {
+        match self {
+            RrBankEvent::Loaded { name, .. } | RrBankEvent::Failed { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Resource)]
+/// Loads soundbanks on the [IoTaskPool], so that large banks don't cause frame hitches.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn load_level_audio(banks: Res<RrBanks>) {
+///     banks.load_async("Level1.bnk");
+/// }
+///
+/// fn on_bank_loaded(mut bank_events: EventReader<RrBankEvent>) {
+///     for event in bank_events.iter() {
+///         if let RrBankEvent::Loaded { name, .. } = event {
+///             // Start the level music...
+///         }
+///     }
+/// }
+/// ```
+pub struct RrBanks {
+    folder: PathBuf,
+    batches: Mutex<Vec<(RrBankBatch, RrBankBatchProgress)>>,
+    next_batch_id: AtomicU64,
+}
+
+impl RrBanks {
+    pub(crate) fn new(folder: PathBuf) -> Self {
+        Self {
+            folder,
+            batches: default(),
+            next_batch_id: AtomicU64::new(0),
+        }
+    }
+
+    /// Starts loading the soundbanks `names` one after the other like [load_bank()], without
+    /// blocking.
+    ///
+    /// The returned [RrBankBatch] tells the progress of the batch, which is also sent as
+    /// [RrBankBatchProgressed] events; handy for loading screens. A failing bank doesn't stop the
+    /// batch.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// fn load_level_audio(banks: Res<RrBanks>, mut commands: Commands) {
+    ///     let batch = banks.load_many(["Level1.bnk", "Level1_Music.bnk", "Level1_VO.bnk"]);
+    ///     commands.insert_resource(LevelAudio(batch));
+    /// }
+    ///
+    /// fn update_loading_bar(mut progressed: EventReader<RrBankBatchProgressed>) {
+    ///     for event in progressed.iter() {
+    ///         info!("Audio: {:.0}%", event.progress.fraction() * 100.);
+    ///     }
+    /// }
+    /// ```
+    pub fn load_many<I, T>(&self, names: I) -> RrBankBatch
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let names: Vec<String> = names.into_iter().map(Into::into).collect();
+        let sizes: Vec<u64> = names
+            .iter()
+            .map(|name| {
+                std::fs::metadata(self.folder.join(name))
+                    .map(|metadata| metadata.len())
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let batch = RrBankBatch {
+            id: self.next_batch_id.fetch_add(1, Ordering::Relaxed),
+            progress: Arc::new(Mutex::new(RrBankBatchProgress {
+                total: names.len(),
+                bytes_total: sizes.iter().sum(),
+                ..default()
+            })),
+        };
+        self.batches
+            .lock()
+            .unwrap()
+            .push((batch.clone(), RrBankBatchProgress::default()));
+
+        let progress = batch.progress.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                for (name, size) in names.iter().zip(sizes) {
+                    let result = load_bank(name);
+
+                    let mut progress = progress.lock().unwrap();
+                    match result {
+                        Ok(_) => progress.loaded += 1,
+                        Err(_) => progress.failed += 1,
+                    }
+                    progress.bytes_loaded += size;
+                }
+            })
+            .detach();
+
+        batch
+    }
+
+    /// Starts loading the soundbank `name` like [load_bank()], without blocking.
+    ///
+    /// An [RrBankEvent] is sent once it is done; you can also await the returned [RrBankLoad] or
+    /// check it every frame.
+    pub fn load_async<T: Into<String>>(&self, name: T) -> RrBankLoad {
+        let name = name.into();
+        let load = RrBankLoad::default();
+
+        let state = load.state.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let result = load_bank(&name);
+
+                let mut state = state.lock().unwrap();
+                state.0 = Some(result);
+                if let Some(waker) = state.1.take() {
+                    waker.wake();
+                }
+            })
+            .detach();
+
+        load
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Progress of an [RrBankBatch].
+pub struct RrBankBatchProgress {
+    /// Banks successfully loaded so far.
+    pub loaded: usize,
+
+    /// Banks that failed to load so far.
+    pub failed: usize,
+
+    pub total: usize,
+
+    /// Size of the bank files processed so far, loaded or not.
+    pub bytes_loaded: u64,
+
+    /// Size of all the bank files of the batch; files that can't be found count for `0`.
+    pub bytes_total: u64,
+}
+
+impl RrBankBatchProgress {
+    /// Whether all the banks of the batch were processed, loaded or not.
+    pub fn is_done(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+
+    /// Processed fraction of the batch, between `0.` and `1.`, weighted by file size when known.
+    pub fn fraction(&self) -> f32 {
+        if self.bytes_total > 0 {
+            self.bytes_loaded as f32 / self.bytes_total as f32
+        } else if self.total > 0 {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        } else {
+            1.
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Handle to a batch of banks loading with [RrBanks::load_many()].
+///
+/// Dropping it doesn't cancel the loading.
+pub struct RrBankBatch {
+    id: u64,
+    progress: Arc<Mutex<RrBankBatchProgress>>,
+}
+
+impl RrBankBatch {
+    /// Identifies this batch in [RrBankBatchProgressed] events.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn progress(&self) -> RrBankBatchProgress {
+        *self.progress.lock().unwrap()
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress().is_done()
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Sent each frame the progress of an [RrBankBatch] changed, until it is done.
+pub struct RrBankBatchProgressed {
+    /// [RrBankBatch::id()] of the batch.
+    pub batch: u64,
+    pub progress: RrBankBatchProgress,
+}
+
+pub(crate) fn send_bank_batch_progress(
+    banks: Res<RrBanks>,
+    mut progressed: EventWriter<RrBankBatchProgressed>,
+) {
+    banks
+        .batches
+        .lock()
+        .unwrap()
+        .retain_mut(|(batch, last_sent)| {
+            let progress = batch.progress();
+            if progress != *last_sent {
+                *last_sent = progress;
+                progressed.send(RrBankBatchProgressed {
+                    batch: batch.id,
+                    progress,
+                });
+            }
+            !progress.is_done()
+        });
+}
+
+type RrBankLoadState = (Option<Result<AkBankID, AkResult>>, Option<Waker>);
+
+#[derive(Default)]
+/// Future resolving when a bank loaded with [RrBanks::load_async()] is done loading.
+///
+/// Dropping it doesn't cancel the loading.
+pub struct RrBankLoad {
+    state: Arc<Mutex<RrBankLoadState>>,
+}
+
+impl RrBankLoad {
+    /// Result of the loading, or `None` while still loading.
+    pub fn result(&self) -> Option<Result<AkBankID, AkResult>> {
+        self.state.lock().unwrap().0
+    }
+}
+
+impl Future for RrBankLoad {
+    type Output = Result<AkBankID, AkResult>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.0 {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.1 = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub(crate) fn send_bank_events(mut bank_events: EventWriter<RrBankEvent>) {
+    let events = std::mem::take(&mut *BANK_EVENTS.lock().unwrap());
+    for event in events {
+        match &event {
+            RrBankEvent::Loaded { name, .. } => debug!("{} loaded", name),
+            RrBankEvent::Failed { name, result } => error!("Couldn't load {} - {}", name, result),
+        }
+        bank_events.send(event);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct QueuedBank {
+    priority: i32,
+    order: u64,
+    name: String,
+}
+
+impl Ord for QueuedBank {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Highest priority first, then first enqueued first
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for QueuedBank {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[derive(Debug, Resource)]
+/// Soundbanks waiting to be loaded, spread over frames: highest priority first, then in
+/// enqueuing order.
+///
+/// Each frame, banks are loaded until [Self::frame_budget] is spent, and an [RrBankEvent] is sent
+/// for each. Loading a bank can't be interrupted, so a large bank may overrun the budget;
+/// use [RrBanks::load_async()] for those.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn enter_level(mut queue: ResMut<RrBankLoadQueue>) {
+///     queue.enqueue("Level1_Music.bnk", 10);
+///     queue.enqueue("Level1_Ambiences.bnk", 0);
+/// }
+/// ```
+pub struct RrBankLoadQueue {
+    /// Defaults to 2 ms.
+    pub frame_budget: Duration,
+    pending: BinaryHeap<QueuedBank>,
+    next_order: u64,
+}
+
+impl Default for RrBankLoadQueue {
+    fn default() -> Self {
+        Self {
+            frame_budget: Duration::from_millis(2),
+            pending: default(),
+            next_order: 0,
+        }
+    }
+}
+
+impl RrBankLoadQueue {
+    /// Queues the soundbank `name` (like `"TheBank.bnk"`) to be loaded like [load_bank()].
+    pub fn enqueue<T: Into<String>>(&mut self, name: T, priority: i32) {
+        self.pending.push(QueuedBank {
+            priority,
+            order: self.next_order,
+            name: name.into(),
+        });
+        self.next_order += 1;
+    }
+
+    /// Removes `name` from the queue; returns whether it was queued.
+    pub fn cancel(&mut self, name: &str) -> bool {
+        let mut pending = std::mem::take(&mut self.pending).into_vec();
+        let len = pending.len();
+        pending.retain(|bank| bank.name != name);
+        let cancelled = pending.len() != len;
+        self.pending = pending.into();
+        cancelled
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+pub(crate) fn process_bank_load_queue(mut queue: ResMut<RrBankLoadQueue>) {
+    if queue.is_empty() || !crate::plugin::is_init_bank_loaded() {
+        return;
+    }
+
+    let start = Instant::now();
+    while start.elapsed() < queue.frame_budget {
+        let Some(QueuedBank { name, .. }) = queue.pending.pop() else {
+            break;
+        };
+
+        // Its result is logged and sent by send_bank_events()
+        let _ = load_bank(&name);
+    }
+}
+
+/// Loads again the banks loaded with [load_bank()], after a sound engine restart.
+pub(crate) fn reload_banks() -> Result<(), AkResult> {
+    for bank in LOADED_BANKS.lock().unwrap().iter() {
+        if let Err(akr) = load_bank_untracked(bank) {
+            error!("Couldn't load {} again - {}", bank, akr);
+            return Err(akr);
+        }
+        debug!("Loaded {} again", bank);
+    }
+    Ok(())
+}
+
+/// Computes the Wwise short ID of a Wwise object name, like `AK::SoundEngine::GetIDFromString`.
+///
+/// Wwise IDs are the 32-bit FNV-1 hash of the lowercase object name.
+pub fn get_id_from_string<T: AsRef<str>>(name: T) -> AkUniqueID {
+    name.as_ref()
+        .to_lowercase()
+        .bytes()
+        .fold(2166136261_u32, |hash, byte| {
+            hash.wrapping_mul(16777619) ^ byte as u32
+        })
+}
+
 /// Helper struct to post events in a fire & forget fashion
 pub struct PostEventAtLocation<'a> {
     inner: RPostEvent<'a>,
+    event_id: AkID<'a>,
     has_flags: bool,
     tmp_id: AkGameObjectID,
     at: AkTransform,
@@ -28,24 +589,28 @@ impl<'a> PostEventAtLocation<'a> {
     pub fn new<T: Into<AkID<'a>>, U: ToAkTransform>(event_id: T, at: U) -> Self {
         // Trick found in the Wwise Unreal Integration... it's worth what it's worth!
         let tmp_id = (&event_id as *const T) as AkGameObjectID;
+        let event_id = event_id.into();
 
         Self {
             inner: RPostEvent::new(tmp_id, event_id),
+            event_id,
             has_flags: false,
             tmp_id,
             at: at.to_ak_transform(),
         }
     }
 
-    /// Add flags before posting. Bitmask: see [AkCallbackType].
-    pub fn add_flags(&mut self, flags: AkCallbackType) -> &mut Self {
-        self.has_flags = flags.0 > AkCallbackType(0).0;
+    /// Add flags before posting. Bitmask: see [AkCallbackType] and [CallbackPreset](crate::CallbackPreset).
+    pub fn add_flags<T: Into<AkCallbackType>>(&mut self, flags: T) -> &mut Self {
+        let flags = flags.into();
+        self.has_flags |= flags.0 > AkCallbackType(0).0;
         self.inner.add_flags(flags);
         self
     }
 
-    /// Set flags before posting. Bitmask: see [AkCallbackType]
-    pub fn flags(&mut self, flags: AkCallbackType) -> &mut Self {
+    /// Set flags before posting. Bitmask: see [AkCallbackType] and [CallbackPreset](crate::CallbackPreset).
+    pub fn flags<T: Into<AkCallbackType>>(&mut self, flags: T) -> &mut Self {
+        let flags = flags.into();
         self.has_flags = flags.0 > AkCallbackType(0).0;
         self.inner.flags(flags);
         self
@@ -56,26 +621,49 @@ impl<'a> PostEventAtLocation<'a> {
     ///
     /// Provide a clone of the [`Res<CallbackChannel>`] resource if you want to receive callbacks
     /// from Wwise (see [Self::flags()], [Self::add_flags()]).
+    ///
+    /// Without flags, uses
+    /// [RriseBasicSettings::default_callback_flags](crate::plugin::RriseBasicSettings::default_callback_flags)
+    /// if you pass a [CallbackChannel].
+    ///
+    /// Returns [AK_INVALID_PLAYING_ID] without posting while audio is disabled (see
+    /// [RrEngine](crate::plugin::RrEngine)).
     pub fn post(&mut self, cb_channel: Option<CallbackChannel>) -> Result<AkPlayingID, AkResult> {
+        if !crate::plugin::is_enabled() {
+            debug!("Audio disabled; {:?} not posted", self.inner);
+            return Ok(AK_INVALID_PLAYING_ID);
+        }
+
         register_game_obj(self.tmp_id)?;
         set_position(self.tmp_id, self.at)?;
         debug!("Registered tmp Wwise emitter {}", self.tmp_id);
 
+        count_post();
+        record_post(self.event_id);
+        if !self.has_flags && cb_channel.is_some() {
+            let flags = crate::plugin::default_callback_flags();
+            self.has_flags = flags.0 > AkCallbackType(0).0;
+            self.inner.flags(flags);
+        }
+
+        let mut subscribers = None;
         let post_result = match (self.has_flags, cb_channel) {
             (false, _) => self.inner.post(),
             (true, None) => {
-                if self.has_flags {
-                    warn!(
-                        "Event {:?} wants callbacks but didn't pass a World; you won't receive bevy events for it",
-                        self.inner,
-                    )
-                }
+                warn!(
+                    "Event {:?} wants callbacks but didn't pass a CallbackChannel; you won't receive bevy events for it",
+                    self.inner,
+                );
                 self.inner.post()
             }
             (true, Some(cb_channel)) => {
-                // self.inner.add_flags(AkCallbackType::AK_EndOfEvent);
+                // Subscriptions forget the playing ID once its event ends
+                if cb_channel.is_subscribed(self.event_id) {
+                    self.inner.add_flags(AkCallbackType::AK_EndOfEvent);
+                    subscribers = Some(cb_channel.clone());
+                }
                 self.inner.post_with_callback(move |cb_info| {
-                    if cb_channel.sender.try_send(cb_info.clone()).is_err() {
+                    if cb_channel.send(cb_info.clone()).is_err() {
                         warn!("Could not send {:?}", cb_info);
                     }
 
@@ -90,6 +678,9 @@ impl<'a> PostEventAtLocation<'a> {
                 })
             }
         };
+        if let (Ok(playing_id), Some(cb_channel)) = (&post_result, subscribers) {
+            cb_channel.track(*playing_id, self.event_id);
+        }
         if let Err(akr) = unregister_game_obj(self.tmp_id) {
             error!(
                 "Couldn't unregister Wwise emitter {}; this might be a leak - {}",
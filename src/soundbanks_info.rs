@@ -0,0 +1,411 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::bus::RrBusInfo;
+use crate::sound_engine::{is_bank_loaded, load_bank};
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use rrise::{AkID, AkResult, AkUniqueID};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A soundbank of the Wwise project, as described in the soundbanks metadata.
+pub struct RrBankInfo {
+    pub id: AkUniqueID,
+    pub name: String,
+
+    /// File name of the bank, to pass to [load_bank()].
+    pub file: String,
+
+    /// `SFX` for banks that aren't localized.
+    pub language: String,
+
+    /// Names of the events included in the bank.
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+/// Banks, events and game syncs of the Wwise project, as described in the soundbanks metadata.
+///
+/// Parsed from the `SoundbanksInfo.json` file generated along the soundbanks; the
+/// [RrBusHierarchy](crate::bus::RrBusHierarchy) is derived from it. Empty if this file can't be
+/// found.
+pub struct RrSoundbanksInfo {
+    banks: Vec<RrBankInfo>,
+    events: HashMap<String, AkUniqueID>,
+    game_parameters: HashMap<String, AkUniqueID>,
+    state_groups: HashMap<String, AkUniqueID>,
+    switch_groups: HashMap<String, AkUniqueID>,
+    /// Switches of each switch group, by group name.
+    switches: HashMap<String, Vec<(String, AkUniqueID)>>,
+    /// Without their parents, which are only resolved by the bus hierarchy.
+    buses: Vec<RrBusInfo>,
+    max_attenuations: HashMap<AkUniqueID, f32>,
+    ids: HashSet<AkUniqueID>,
+}
+
+impl RrSoundbanksInfo {
+    pub(crate) fn from_metadata(metadata: &Value) -> Self {
+        let mut info = Self::default();
+
+        let banks = metadata["SoundBanksInfo"]["SoundBanks"].as_array();
+        for bank in banks.into_iter().flatten() {
+            let (Some(id), Some(name), Some(file)) = (
+                bank["Id"].as_str().and_then(|id| id.parse().ok()),
+                bank["ShortName"].as_str(),
+                bank["Path"].as_str(),
+            ) else {
+                continue;
+            };
+
+            let mut events = vec![];
+            for (event_name, event_id) in named_ids(bank, "IncludedEvents") {
+                events.push(event_name.clone());
+                info.ids.insert(event_id);
+                info.events.insert(event_name, event_id);
+            }
+
+            // Only there if soundbanks are generated with "Max Radius" info
+            for event in bank["IncludedEvents"].as_array().into_iter().flatten() {
+                if let (Some(event_id), Some(max_attenuation)) = (
+                    event["Id"].as_str().and_then(|id| id.parse().ok()),
+                    event["MaxAttenuation"]
+                        .as_str()
+                        .and_then(|max| max.parse().ok()),
+                ) {
+                    info.max_attenuations.insert(event_id, max_attenuation);
+                }
+            }
+
+            for (key, known) in [
+                ("GameParameters", &mut info.game_parameters),
+                ("StateGroups", &mut info.state_groups),
+                ("SwitchGroups", &mut info.switch_groups),
+            ] {
+                for (object_name, object_id) in named_ids(bank, key) {
+                    info.ids.insert(object_id);
+                    known.insert(object_name, object_id);
+                }
+            }
+
+            for group in bank["StateGroups"].as_array().into_iter().flatten() {
+                info.ids
+                    .extend(named_ids(group, "States").map(|(_, state_id)| state_id));
+            }
+
+            for group in bank["SwitchGroups"].as_array().into_iter().flatten() {
+                let switches: Vec<_> = named_ids(group, "Switches").collect();
+                info.ids
+                    .extend(switches.iter().map(|&(_, switch_id)| switch_id));
+                if let Some(group_name) = group["Name"].as_str() {
+                    info.switches.insert(group_name.to_string(), switches);
+                }
+            }
+
+            for (key, aux) in [("Busses", false), ("AuxBusses", true)] {
+                for bus in bank[key].as_array().into_iter().flatten() {
+                    let Some((name, id)) = named_id(bus) else {
+                        continue;
+                    };
+                    info.ids.insert(id);
+
+                    // Buses are listed in every bank using them
+                    let Some(path) = bus["ObjectPath"].as_str() else {
+                        continue;
+                    };
+                    if info.buses.iter().any(|known| known.id == id) {
+                        continue;
+                    }
+
+                    info.buses.push(RrBusInfo {
+                        id,
+                        name,
+                        path: path.to_string(),
+                        parent: None,
+                        aux,
+                    });
+                }
+            }
+
+            info.ids.insert(id);
+            info.banks.push(RrBankInfo {
+                id,
+                name: name.to_string(),
+                file: file.to_string(),
+                language: bank["Language"].as_str().unwrap_or("SFX").to_string(),
+                events,
+            });
+        }
+
+        debug!(
+            "Found {} banks and {} events in the soundbanks metadata",
+            info.banks.len(),
+            info.events.len()
+        );
+        info
+    }
+
+    pub fn banks(&self) -> impl Iterator<Item = &RrBankInfo> {
+        self.banks.iter()
+    }
+
+    /// Names of all the events of the project.
+    pub fn events(&self) -> impl Iterator<Item = &str> {
+        self.events.keys().map(String::as_str)
+    }
+
+    pub fn event_id(&self, name: &str) -> Option<AkUniqueID> {
+        self.events.get(name).copied()
+    }
+
+    /// Name of the event `id`.
+    pub fn event_name(&self, id: AkUniqueID) -> Option<&str> {
+        self.events
+            .iter()
+            .find(|(_, &event_id)| event_id == id)
+            .map(|(name, _)| name.as_str())
+    }
+
+    pub fn game_parameter_id(&self, name: &str) -> Option<AkUniqueID> {
+        self.game_parameters.get(name).copied()
+    }
+
+    pub fn state_group_id(&self, name: &str) -> Option<AkUniqueID> {
+        self.state_groups.get(name).copied()
+    }
+
+    pub fn switch_group_id(&self, name: &str) -> Option<AkUniqueID> {
+        self.switch_groups.get(name).copied()
+    }
+
+    /// `(Name, Id)` of the switch groups, with their switches.
+    pub(crate) fn switch_groups(
+        &self,
+    ) -> impl Iterator<Item = (&str, AkUniqueID, &[(String, AkUniqueID)])> {
+        self.switch_groups.iter().map(|(name, &id)| {
+            let switches = self
+                .switches
+                .get(name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            (name.as_str(), id, switches)
+        })
+    }
+
+    pub(crate) fn state_group_ids(&self) -> impl Iterator<Item = AkUniqueID> + '_ {
+        self.state_groups.values().copied()
+    }
+
+    pub(crate) fn game_parameter_ids(&self) -> impl Iterator<Item = AkUniqueID> + '_ {
+        self.game_parameters.values().copied()
+    }
+
+    pub(crate) fn buses(&self) -> &[RrBusInfo] {
+        &self.buses
+    }
+
+    /// Distance beyond which `event` can't be heard, according to the attenuations of its sounds.
+    ///
+    /// Useful to skip spawning emitters nobody can hear. `None` if the event is unknown, has no
+    /// attenuation, or the soundbanks were generated without the "Max Radius" info (Project
+    /// Settings > SoundBanks > Generate Max Radius Information).
+    pub fn max_attenuation_distance<'a, T: Into<AkID<'a>>>(&self, event: T) -> Option<f32> {
+        let event_id = match event.into() {
+            AkID::ID(id) => id,
+            AkID::Name(name) => crate::sound_engine::get_id_from_string(name),
+        };
+        self.max_attenuations.get(&event_id).copied()
+    }
+
+    /// Banks including the event `name`.
+    pub fn banks_of_event<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a RrBankInfo> {
+        self.banks
+            .iter()
+            .filter(move |bank| bank.events.iter().any(|event| event == name))
+    }
+
+    /// Whether `id` names or identifies a bank, event, bus or game sync of the project.
+    ///
+    /// Useful to validate IDs coming from data files, before they end up failing silently in
+    /// Wwise.
+    pub fn is_valid<'a, T: Into<AkID<'a>>>(&self, id: T) -> bool {
+        match id.into() {
+            AkID::ID(id) => self.ids.contains(&id),
+            AkID::Name(name) => self
+                .ids
+                .contains(&crate::sound_engine::get_id_from_string(name)),
+        }
+    }
+
+    /// Loads the banks including the event `name` with [load_bank()], unless they're already
+    /// loaded.
+    ///
+    /// Fails with [AkResult::AK_IDNotFound] if no bank includes this event.
+    pub fn load_banks_of_event(&self, name: &str) -> Result<(), AkResult> {
+        let mut found = false;
+        for bank in self.banks_of_event(name) {
+            found = true;
+            if !is_bank_loaded(&bank.file) {
+                load_bank(&bank.file)?;
+                debug!("Loaded {} for event '{}'", bank.file, name);
+            }
+        }
+
+        if found {
+            Ok(())
+        } else {
+            error!("No soundbank includes event '{}'", name);
+            Err(AkResult::AK_IDNotFound)
+        }
+    }
+}
+
+/// `(Name, Id)` of the objects listed under `key` in `object`.
+fn named_ids<'a>(object: &'a Value, key: &str) -> impl Iterator<Item = (String, AkUniqueID)> + 'a {
+    object[key]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(named_id)
+}
+
+/// `(Name, Id)` of `object`.
+fn named_id(object: &Value) -> Option<(String, AkUniqueID)> {
+    Some((
+        object["Name"].as_str()?.to_string(),
+        object["Id"].as_str()?.parse().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bus::RrBusHierarchy;
+    use serde_json::json;
+
+    const MASTER: &str = "\\Master-Mixer Hierarchy\\Default Work Unit\\Master Audio Bus";
+
+    fn fixture() -> RrSoundbanksInfo {
+        let master_bus = json!({ "Id": "100", "Name": "Master Audio Bus", "ObjectPath": MASTER });
+        RrSoundbanksInfo::from_metadata(&json!({
+            "SoundBanksInfo": {
+                "SoundBanks": [
+                    {
+                        "Id": "1",
+                        "ShortName": "Init",
+                        "Path": "Init.bnk",
+                        "Busses": [
+                            master_bus,
+                            {
+                                "Id": "101",
+                                "Name": "Music",
+                                "ObjectPath": format!("{MASTER}\\Music"),
+                            },
+                        ],
+                        "AuxBusses": [
+                            {
+                                "Id": "102",
+                                "Name": "Reverb",
+                                "ObjectPath": format!("{MASTER}\\Reverb"),
+                            },
+                        ],
+                    },
+                    {
+                        "Id": "2",
+                        "ShortName": "Footsteps",
+                        "Path": "Footsteps.bnk",
+                        "IncludedEvents": [
+                            { "Id": "10", "Name": "Play_Footstep", "MaxAttenuation": "25" },
+                            { "Id": "11", "Name": "Stop_Footstep" },
+                        ],
+                        "SwitchGroups": [
+                            {
+                                "Id": "20",
+                                "Name": "Surface",
+                                "Switches": [
+                                    { "Id": "21", "Name": "Grass" },
+                                    { "Id": "22", "Name": "Stone" },
+                                ],
+                            },
+                        ],
+                        "Busses": [master_bus],
+                    },
+                    {
+                        "Id": "3",
+                        "ShortName": "Dialogues",
+                        "Path": "English(US)/Dialogues.bnk",
+                        "Language": "English(US)",
+                        "IncludedEvents": [{ "Id": "12", "Name": "Play_Greeting" }],
+                    },
+                    { "ShortName": "NoId", "Path": "NoId.bnk" },
+                ],
+            },
+        }))
+    }
+
+    #[test]
+    fn events_and_banks() {
+        let info = fixture();
+
+        let banks: Vec<_> = info.banks().map(|bank| bank.name.as_str()).collect();
+        assert_eq!(banks, ["Init", "Footsteps", "Dialogues"]);
+        assert_eq!(info.banks().next().unwrap().language, "SFX");
+        assert_eq!(info.banks().last().unwrap().language, "English(US)");
+
+        assert_eq!(info.event_id("Play_Footstep"), Some(10));
+        assert_eq!(info.event_id("Play_Unknown"), None);
+        assert_eq!(info.event_name(12), Some("Play_Greeting"));
+
+        let footstep_banks: Vec<_> = info
+            .banks_of_event("Stop_Footstep")
+            .map(|bank| bank.file.as_str())
+            .collect();
+        assert_eq!(footstep_banks, ["Footsteps.bnk"]);
+        assert_eq!(info.banks_of_event("Play_Unknown").count(), 0);
+    }
+
+    #[test]
+    fn switch_groups() {
+        let info = fixture();
+
+        assert_eq!(info.switch_group_id("Surface"), Some(20));
+        let (name, id, switches) = info.switch_groups().next().unwrap();
+        assert_eq!((name, id), ("Surface", 20));
+        assert_eq!(
+            switches,
+            [("Grass".to_string(), 21), ("Stone".to_string(), 22)]
+        );
+
+        assert!(info.is_valid(21));
+        assert!(!info.is_valid(23));
+    }
+
+    #[test]
+    fn bus_parents_from_object_path() {
+        let info = fixture();
+        assert_eq!(info.buses().len(), 3);
+
+        let hierarchy = RrBusHierarchy::from_info(&info);
+        let roots: Vec<_> = hierarchy.roots().map(|bus| bus.id).collect();
+        assert_eq!(roots, [100]);
+
+        let mut children: Vec<_> = hierarchy.children(100).map(|bus| bus.id).collect();
+        children.sort();
+        assert_eq!(children, [101, 102]);
+
+        let reverb = hierarchy.get_by_name("Reverb").unwrap();
+        assert_eq!(reverb.parent, Some(100));
+        assert!(reverb.aux);
+        assert!(!hierarchy.get(101).unwrap().aux);
+    }
+
+    #[test]
+    fn max_attenuation_distance() {
+        let info = fixture();
+
+        assert_eq!(info.max_attenuation_distance(10), Some(25.));
+        assert_eq!(info.max_attenuation_distance(11), None);
+        assert_eq!(info.max_attenuation_distance(13), None);
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+//! Typed parameters for the SoundSeed procedural plugins.
+//!
+//! Wwise plugin properties can't be set from the game directly: in your Wwise project, bind each
+//! property to the game parameter named in [RrSoundSeed] (the default names follow the
+//! `SoundSeed_<Parameter>` convention), with a linear curve over the range given in its doc. The
+//! helpers then take values in world units.
+//!
+//! *Remark* SoundSeed plugins aren't built into Rrise; ship them as shared libraries (see
+//! [RriseBasicSettings::plugin_dll_path](crate::plugin::RriseBasicSettings::plugin_dll_path)).
+
+use crate::diagnostics::count_set_rtpc;
+use crate::emitter_listener::{ak_id_of, RrObjDesc};
+use bevy::prelude::*;
+use rrise::game_syncs::SetRtpcValue;
+use rrise::{AkID, AkResult, AkRtpcValue};
+
+#[derive(Debug, Clone, Resource)]
+/// Game parameters bound to the SoundSeed plugin properties in your Wwise project.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn on_collisions(mut collisions: EventReader<Collision>, soundseed: Res<RrSoundSeed>) {
+///     for collision in collisions.iter() {
+///         let _ = soundseed.set_impact(collision.entity, collision.relative_speed, collision.mass);
+///     }
+/// }
+/// ```
+pub struct RrSoundSeed {
+    /// SoundSeed Air Wind: average wind speed, in world units per second, over `0..50`.
+    ///
+    /// Defaults to `SoundSeed_WindSpeed`.
+    pub wind_speed_rtpc: AkID<'static>,
+
+    /// SoundSeed Air Woosh: speed of the object moving through the air, in world units per
+    /// second, over `0..50`.
+    ///
+    /// Defaults to `SoundSeed_WooshSpeed`.
+    pub woosh_speed_rtpc: AkID<'static>,
+
+    /// SoundSeed Impact: relative speed of the colliding objects, in world units per second, over
+    /// `0..20`; usually bound to the gain and the mode frequency variation.
+    ///
+    /// Defaults to `SoundSeed_ImpactVelocity`.
+    pub impact_velocity_rtpc: AkID<'static>,
+
+    /// SoundSeed Impact: mass of the impacted object, in kilograms, over `0..100`; usually bound
+    /// to the frequency of the modes, heavier objects sounding lower.
+    ///
+    /// Defaults to `SoundSeed_ImpactMass`.
+    pub impact_mass_rtpc: AkID<'static>,
+}
+
+impl Default for RrSoundSeed {
+    fn default() -> Self {
+        Self {
+            wind_speed_rtpc: AkID::Name("SoundSeed_WindSpeed"),
+            woosh_speed_rtpc: AkID::Name("SoundSeed_WooshSpeed"),
+            impact_velocity_rtpc: AkID::Name("SoundSeed_ImpactVelocity"),
+            impact_mass_rtpc: AkID::Name("SoundSeed_ImpactMass"),
+        }
+    }
+}
+
+impl RrSoundSeed {
+    /// Sets the wind speed globally, for all SoundSeed Wind sources.
+    pub fn set_wind_speed(&self, speed: f32) -> Result<(), AkResult> {
+        set_on(None, self.wind_speed_rtpc, speed.max(0.))
+    }
+
+    /// Sets the speed of the SoundSeed Woosh sources playing on the emitter of `e`.
+    pub fn set_woosh_speed(&self, e: Entity, speed: f32) -> Result<(), AkResult> {
+        set_on(Some(e), self.woosh_speed_rtpc, speed.max(0.))
+    }
+
+    /// Sets the impact parameters on the emitter of `e`; call it right before posting the impact
+    /// event on it.
+    ///
+    /// Stops at the first failure.
+    pub fn set_impact(&self, e: Entity, velocity: f32, mass: f32) -> Result<(), AkResult> {
+        set_on(Some(e), self.impact_velocity_rtpc, velocity.abs())?;
+        set_on(Some(e), self.impact_mass_rtpc, mass.max(0.))
+    }
+}
+
+/// Sets `rtpc` on the game object of `e`, or globally.
+fn set_on(e: Option<Entity>, rtpc: AkID<'static>, value: f32) -> Result<(), AkResult> {
+    let mut set_rtpc = SetRtpcValue::new(rtpc, value as AkRtpcValue);
+    if let Some(e) = e {
+        set_rtpc = set_rtpc.for_target(ak_id_of(e));
+    }
+
+    count_set_rtpc();
+    if let Err(akr) = set_rtpc.set() {
+        match e {
+            Some(e) => error!(
+                "Couldn't set RTPC '{}' on {} - {}",
+                rtpc,
+                RrObjDesc::new(Some(e), None),
+                akr
+            ),
+            None => error!("Couldn't set RTPC '{}' - {}", rtpc, akr),
+        }
+        return Err(akr);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::{ak_id_of, RrObjDesc, RrRegistered};
+use bevy::prelude::*;
+use rrise::game_syncs::set_switch;
+use rrise::AkID;
+
+#[derive(Debug, Clone, Copy, Component)]
+/// Switch value of the emitter on the same entity, set in Wwise whenever this component is added
+/// or changed.
+///
+/// The switch is set when the emitter gets registered, before any auto post. Changes are set at
+/// the end of the frame, so events posted later in the frame still use the previous value.
+///
+/// `group` and `value` should both be names or both be IDs.
+///
+/// ### Example
+/// ```rust,ignore
+/// fn update_surfaces(mut feet: Query<(&GroundContact, &mut RrSwitch)>) {
+///     for (contact, mut switch) in feet.iter_mut() {
+///         switch.value = contact.surface_name().into();
+///     }
+/// }
+/// ```
+pub struct RrSwitch {
+    pub group: AkID<'static>,
+    pub value: AkID<'static>,
+}
+
+impl RrSwitch {
+    pub fn new<T: Into<AkID<'static>>>(group: T, value: T) -> Self {
+        Self {
+            group: group.into(),
+            value: value.into(),
+        }
+    }
+
+    pub(crate) fn apply(&self, e: Entity) {
+        if let Err(akr) = set_switch(self.group, self.value, ak_id_of(e)) {
+            error!(
+                "Couldn't set switch '{}' to '{}' on {} - {}",
+                self.group,
+                self.value,
+                RrObjDesc::new(Some(e), None),
+                akr
+            );
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn sync_switch_components(
+    query: Query<(Entity, &RrSwitch, ChangeTrackers<RrSwitch>), With<RrRegistered>>,
+    mut last_generation: Local<Option<u32>>,
+) {
+    // Game objects got registered again, without their switch values
+    let generation = crate::plugin::sound_engine_generation();
+    let restarted = matches!(*last_generation, Some(g) if g != generation);
+    *last_generation = Some(generation);
+
+    for (e, switch, tracker) in query.iter() {
+        if restarted || tracker.is_changed() {
+            switch.apply(e);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+//! Audio QA helpers; not available in `wwrelease`.
+
+use crate::sequence::{RrSequence, RrSequenceStep};
+use crate::soundbanks_info::RrSoundbanksInfo;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rrise::{AkID, AkUniqueID};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, Resource)]
+/// Switch groups of the Wwise project and their switches, as described in the soundbanks
+/// metadata (see [RrSoundbanksInfo] to generate it).
+pub struct RrSwitchGroups {
+    groups: HashMap<String, (AkUniqueID, Vec<(String, AkUniqueID)>)>,
+}
+
+impl RrSwitchGroups {
+    pub(crate) fn from_info(info: &RrSoundbanksInfo) -> Self {
+        let groups = info
+            .switch_groups()
+            .map(|(name, id, switches)| (name.to_string(), (id, switches.to_vec())))
+            .collect();
+
+        Self { groups }
+    }
+
+    /// Names of the switches of `group`.
+    pub fn switches(&self, group: &str) -> Option<impl Iterator<Item = &str>> {
+        self.groups
+            .get(group)
+            .map(|(_, switches)| switches.iter().map(|(name, _)| name.as_str()))
+    }
+}
+
+impl RrSequence {
+    /// Sets each switch of `group` in turn on the emitter and posts `event`, waiting for it to
+    /// end plus `spacing` before moving to the next one; logs which switch is audited.
+    ///
+    /// A single command then tells if all footstep surfaces or material variants are hooked up.
+    /// Does nothing but log an error if `group` isn't in the [RrSwitchGroups].
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// fn audit_footsteps(mut commands: Commands, groups: Res<RrSwitchGroups>) {
+    ///     commands.spawn(RrEmitterBundle::new(Vec3::ZERO)).insert(RrSequence::new().audit_switches(
+    ///         &groups,
+    ///         "Surface",
+    ///         "Play_Footstep",
+    ///         Duration::from_millis(500),
+    ///     ));
+    /// }
+    /// ```
+    pub fn audit_switches<T: Into<AkID<'static>>>(
+        mut self,
+        switch_groups: &RrSwitchGroups,
+        group: &str,
+        event: T,
+        spacing: Duration,
+    ) -> Self {
+        let Some((group_id, switches)) = switch_groups.groups.get(group) else {
+            error!("Can't audit unknown switch group '{}'", group);
+            return self;
+        };
+
+        let event = event.into();
+        for (name, id) in switches {
+            self.steps.push_back(RrSequenceStep::Log(format!(
+                "auditing switch '{}' of group '{}' with '{}'",
+                name, group, event
+            )));
+            self = self
+                .set_switch(AkID::ID(*group_id), AkID::ID(*id))
+                .post(event)
+                .wait_end()
+                .wait(spacing);
+        }
+
+        self
+    }
+}
@@ -0,0 +1,390 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::{RrEmitter, RrObjDesc, RrRegistered};
+use crate::plugin::CallbackChannel;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rrise::{AkID, AkPlayingID, AK_INVALID_PLAYING_ID};
+
+/// Identifies a voice line enqueued in the [RrVoiceQueue].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RrVoiceLineId(u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What happens to a voice line enqueued while its channel is busy.
+pub enum RrInterruptPolicy {
+    /// Wait for the lines before it to finish. Lines are played by priority, then in the order
+    /// they were enqueued.
+    Wait,
+
+    /// If the line currently playing on the channel has a lower priority, stop it and play this
+    /// one right away. Otherwise, [wait](Self::Wait).
+    InterruptLowerPriority,
+
+    /// Discard this line if anything is playing or waiting on the channel.
+    DropIfBusy,
+}
+
+#[derive(Debug, Clone)]
+/// A line of dialogue to play on a speaker entity.
+pub struct RrVoiceLine {
+    /// Event to post.
+    pub event: AkID<'static>,
+
+    /// Entity on which the event gets posted; it must hold an [RrEmitter].
+    pub speaker: Entity,
+
+    /// Higher priority lines are played first.
+    ///
+    /// Defaults to `0`.
+    pub priority: u8,
+
+    /// Lines of a same channel are played one after the other; lines of different channels can
+    /// overlap.
+    ///
+    /// Defaults to `0`.
+    pub channel: u32,
+
+    /// Defaults to [RrInterruptPolicy::Wait].
+    pub interrupt_policy: RrInterruptPolicy,
+}
+
+impl RrVoiceLine {
+    pub fn new<T: Into<AkID<'static>>>(event: T, speaker: Entity) -> Self {
+        Self {
+            event: event.into(),
+            speaker,
+            priority: 0,
+            channel: 0,
+            interrupt_policy: RrInterruptPolicy::Wait,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn on_channel(mut self, channel: u32) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    pub fn with_interrupt_policy(mut self, interrupt_policy: RrInterruptPolicy) -> Self {
+        self.interrupt_policy = interrupt_policy;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Sent when a voice line starts playing.
+pub struct RrVoiceLineStarted {
+    pub id: RrVoiceLineId,
+    pub speaker: Entity,
+    pub event: AkID<'static>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Why a voice line ended.
+pub enum RrVoiceLineEnd {
+    /// The line played until its end.
+    Completed,
+
+    /// The line was stopped by a higher priority line or [RrVoiceQueue::clear()], or its speaker
+    /// was despawned.
+    Interrupted,
+
+    /// The line was removed by [RrVoiceQueue::clear()] before it could play.
+    Cleared,
+
+    /// The line couldn't be posted.
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+/// Sent when a voice line ends or is discarded, whether it started playing or not.
+///
+/// Lines discarded right away by [RrInterruptPolicy::DropIfBusy] don't send this.
+pub struct RrVoiceLineFinished {
+    pub id: RrVoiceLineId,
+    pub speaker: Entity,
+    pub reason: RrVoiceLineEnd,
+}
+
+#[derive(Debug)]
+struct PlayingLine {
+    id: RrVoiceLineId,
+    line: RrVoiceLine,
+    playing_id: AkPlayingID,
+}
+
+#[derive(Debug, Default)]
+struct VoiceChannel {
+    pending: Vec<(RrVoiceLineId, RrVoiceLine)>,
+    current: Option<PlayingLine>,
+    interrupt_current: bool,
+}
+
+impl VoiceChannel {
+    fn is_busy(&self) -> bool {
+        self.current.is_some() || !self.pending.is_empty()
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+/// Queue of voice over lines, played sequentially per channel.
+///
+/// Enqueue lines from your systems, then react to [RrVoiceLineStarted] and
+/// [RrVoiceLineFinished] events to drive subtitles or your narrative logic.
+///
+/// *Remark* Wwise can't stop a single playing ID with the current bindings; interrupting a line
+/// stops everything playing on its speaker [RrEmitter].
+pub struct RrVoiceQueue {
+    next_id: u64,
+    channels: HashMap<u32, VoiceChannel>,
+    cleared: Vec<(RrVoiceLineId, Entity)>,
+}
+
+impl RrVoiceQueue {
+    /// Enqueues `line` according to its interrupt policy.
+    ///
+    /// Returns [None] if the line was discarded.
+    pub fn enqueue(&mut self, line: RrVoiceLine) -> Option<RrVoiceLineId> {
+        let channel = self.channels.entry(line.channel).or_default();
+
+        if line.interrupt_policy == RrInterruptPolicy::DropIfBusy && channel.is_busy() {
+            debug!(
+                "Voice line '{}' dropped: channel {} is busy",
+                line.event, line.channel
+            );
+            return None;
+        }
+
+        let id = RrVoiceLineId(self.next_id);
+        self.next_id += 1;
+
+        let interrupts = line.interrupt_policy == RrInterruptPolicy::InterruptLowerPriority
+            && matches!(&channel.current, Some(current) if current.line.priority < line.priority);
+        if interrupts {
+            channel.interrupt_current = true;
+            channel.pending.insert(0, (id, line));
+        } else {
+            let index = channel
+                .pending
+                .iter()
+                .position(|(_, pending)| pending.priority < line.priority)
+                .unwrap_or(channel.pending.len());
+            channel.pending.insert(index, (id, line));
+        }
+
+        Some(id)
+    }
+
+    /// Discards all lines waiting on `channel` and interrupts the one playing.
+    pub fn clear(&mut self, channel: u32) {
+        if let Some(channel) = self.channels.get_mut(&channel) {
+            self.cleared.extend(
+                channel
+                    .pending
+                    .drain(..)
+                    .map(|(id, line)| (id, line.speaker)),
+            );
+            channel.interrupt_current = channel.current.is_some();
+        }
+    }
+
+    /// Whether a line is playing or waiting on `channel`.
+    pub fn is_busy(&self, channel: u32) -> bool {
+        matches!(self.channels.get(&channel), Some(c) if c.is_busy())
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.channels
+            .values()
+            .map(|c| c.pending.len() + c.current.is_some() as usize)
+            .sum::<usize>()
+            + self.cleared.len()
+    }
+}
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn process_voice_queue(
+    mut queue: ResMut<RrVoiceQueue>,
+    mut speakers: Query<(&mut RrEmitter, Option<&RrRegistered>)>,
+    cb_channel: Res<CallbackChannel>,
+    mut started: EventWriter<RrVoiceLineStarted>,
+    mut finished: EventWriter<RrVoiceLineFinished>,
+) {
+    // Lines wait for audio to be enabled again
+    if !crate::plugin::is_enabled() {
+        return;
+    }
+
+    let queue = &mut *queue;
+
+    for (id, speaker) in queue.cleared.drain(..) {
+        finished.send(RrVoiceLineFinished {
+            id,
+            speaker,
+            reason: RrVoiceLineEnd::Cleared,
+        });
+    }
+
+    for channel in queue.channels.values_mut() {
+        if let Some(current) = &channel.current {
+            let speaker = speakers.get(current.line.speaker).ok();
+            let still_playing = matches!(speaker, Some((rr, _))
+                if rr.is_playing_id(current.playing_id));
+
+            if still_playing && !channel.interrupt_current {
+                continue;
+            }
+
+            if still_playing {
+                speaker.unwrap().0.stop();
+            }
+
+            finished.send(RrVoiceLineFinished {
+                id: current.id,
+                speaker: current.line.speaker,
+                reason: if still_playing || speaker.is_none() {
+                    RrVoiceLineEnd::Interrupted
+                } else {
+                    RrVoiceLineEnd::Completed
+                },
+            });
+            channel.current = None;
+        }
+        channel.interrupt_current = false;
+
+        while !channel.pending.is_empty() {
+            let speaker = channel.pending[0].1.speaker;
+            let Ok((mut rr, registered)) = speakers.get_mut(speaker) else {
+                let (id, line) = channel.pending.remove(0);
+                error!(
+                    "Couldn't play voice line '{}': speaker {} has no RrEmitter",
+                    line.event,
+                    RrObjDesc::new(Some(speaker), None)
+                );
+                finished.send(RrVoiceLineFinished {
+                    id,
+                    speaker,
+                    reason: RrVoiceLineEnd::Failed,
+                });
+                continue;
+            };
+
+            if registered.is_none() {
+                // Give the speaker a chance to get registered
+                break;
+            }
+
+            let (id, line) = channel.pending.remove(0);
+            let flags = rr.flags;
+            let playing_id = rr.post_event(line.event, flags, Some(cb_channel.clone()));
+            if playing_id == AK_INVALID_PLAYING_ID {
+                finished.send(RrVoiceLineFinished {
+                    id,
+                    speaker,
+                    reason: RrVoiceLineEnd::Failed,
+                });
+                continue;
+            }
+
+            started.send(RrVoiceLineStarted {
+                id,
+                speaker,
+                event: line.event,
+            });
+            channel.current = Some(PlayingLine {
+                id,
+                line,
+                playing_id,
+            });
+            break;
+        }
+    }
+
+    // Don't keep idle channels around
+    queue.channels.retain(|_, channel| channel.is_busy());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(event: u32, priority: u8) -> RrVoiceLine {
+        RrVoiceLine::new(event, Entity::from_raw(0)).with_priority(priority)
+    }
+
+    fn pending_events(queue: &RrVoiceQueue) -> Vec<u32> {
+        queue.channels[&0]
+            .pending
+            .iter()
+            .map(|(_, line)| match line.event {
+                AkID::ID(id) => id,
+                AkID::Name(name) => panic!("unexpected event name {name}"),
+            })
+            .collect()
+    }
+
+    fn start_playing(queue: &mut RrVoiceQueue, line: RrVoiceLine) {
+        let id = queue.enqueue(line).unwrap();
+        let channel = queue.channels.get_mut(&0).unwrap();
+        let (_, line) = channel.pending.remove(0);
+        channel.current = Some(PlayingLine {
+            id,
+            line,
+            playing_id: 1,
+        });
+    }
+
+    #[test]
+    fn priority_ordering() {
+        let mut queue = RrVoiceQueue::default();
+        queue.enqueue(line(1, 0));
+        queue.enqueue(line(2, 5));
+        queue.enqueue(line(3, 0));
+        queue.enqueue(line(4, 5));
+
+        assert_eq!(pending_events(&queue), [2, 4, 1, 3]);
+        assert_eq!(queue.len(), 4);
+    }
+
+    #[test]
+    fn interrupt_lower_priority() {
+        let mut queue = RrVoiceQueue::default();
+        start_playing(&mut queue, line(1, 1));
+        queue.enqueue(line(2, 9));
+
+        queue.enqueue(line(3, 2).with_interrupt_policy(RrInterruptPolicy::InterruptLowerPriority));
+        assert_eq!(pending_events(&queue), [3, 2]);
+        assert!(queue.channels[&0].interrupt_current);
+    }
+
+    #[test]
+    fn no_interrupt_of_higher_priority() {
+        let mut queue = RrVoiceQueue::default();
+        start_playing(&mut queue, line(1, 5));
+
+        queue.enqueue(line(2, 2).with_interrupt_policy(RrInterruptPolicy::InterruptLowerPriority));
+        assert_eq!(pending_events(&queue), [2]);
+        assert!(!queue.channels[&0].interrupt_current);
+    }
+
+    #[test]
+    fn drop_if_busy() {
+        let mut queue = RrVoiceQueue::default();
+        let drop_if_busy =
+            |event| line(event, 0).with_interrupt_policy(RrInterruptPolicy::DropIfBusy);
+
+        assert!(queue.enqueue(drop_if_busy(1)).is_some());
+        assert!(queue.enqueue(drop_if_busy(2)).is_none());
+        assert_eq!(pending_events(&queue), [1]);
+
+        // Other channels aren't busy
+        assert!(queue.enqueue(drop_if_busy(3).on_channel(1)).is_some());
+    }
+}
@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::emitter_listener::unique_id;
+use crate::soundbanks_info::RrSoundbanksInfo;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rrise::{AkID, AkUniqueID};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Resource)]
+/// Records which events get posted during a run, so that the next runs load the banks of the most
+/// posted ones right after Init.bnk, before your startup systems run. This avoids hitches when
+/// these events are first posted in the opening seconds of gameplay.
+///
+/// Insert it before adding the plugin. The profile is read at startup and written back when the
+/// sound engine terminates. Counts of previous runs are halved each run, so that events nobody
+/// posts anymore eventually leave the profile.
+///
+/// Banks are found with the soundbanks metadata (see [RrSoundbanksInfo]), so nothing is loaded
+/// without it.
+///
+/// *Remark* Game objects aren't pre-registered: their IDs derive from entity indices, unknown
+/// before spawning, and registering one is cheap compared to loading a bank.
+///
+/// ### Example
+/// ```rust,ignore
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .insert_resource(RrWarmStart::new("audio_profile.json"))
+///     .add_plugin(RrisePlugin::default())
+///     .run();
+/// ```
+pub struct RrWarmStart {
+    /// JSON file holding the profile.
+    pub profile: PathBuf,
+
+    /// How many of the most posted events get their banks loaded at startup.
+    ///
+    /// Defaults to `32`.
+    pub max_events: usize,
+}
+
+impl RrWarmStart {
+    pub fn new<T: Into<PathBuf>>(profile: T) -> Self {
+        Self {
+            profile: profile.into(),
+            max_events: 32,
+        }
+    }
+
+    pub fn with_max_events(mut self, max_events: usize) -> Self {
+        self.max_events = max_events;
+        self
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Profile {
+    /// Number of posts of each event ID.
+    events: HashMap<AkUniqueID, u32>,
+}
+
+/// Profile being recorded, with the file to write it to; `None` when not warm starting.
+static RECORDING: Mutex<Option<(PathBuf, Profile)>> = Mutex::new(None);
+
+/// Counts a post of `event` in the profile being recorded, if any.
+pub(crate) fn record_post(event: AkID) {
+    if let Some((_, profile)) = RECORDING.lock().unwrap().as_mut() {
+        *profile.events.entry(unique_id(event)).or_default() += 1;
+    }
+}
+
+/// Loads the banks of the most posted events of the profile, and starts recording this run.
+pub(crate) fn warm_start(settings: &RrWarmStart, info: &RrSoundbanksInfo) {
+    let mut profile = match std::fs::read_to_string(&settings.profile) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            warn!(
+                "Ignoring invalid warm start profile {} - {}",
+                settings.profile.to_string_lossy(),
+                e
+            );
+            Profile::default()
+        }),
+        Err(e) => {
+            debug!(
+                "No warm start profile: couldn't read {} - {}",
+                settings.profile.to_string_lossy(),
+                e
+            );
+            Profile::default()
+        }
+    };
+
+    let mut events: Vec<_> = profile.events.iter().map(|(&id, &n)| (id, n)).collect();
+    events.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+    for &(event_id, _) in events.iter().take(settings.max_events) {
+        if let Some(name) = info.event_name(event_id) {
+            let _ = info.load_banks_of_event(name);
+        }
+    }
+    debug!(
+        "Warm started the banks of {} events",
+        events.len().min(settings.max_events)
+    );
+
+    profile.events.retain(|_, posts| {
+        *posts /= 2;
+        *posts > 0
+    });
+    *RECORDING.lock().unwrap() = Some((settings.profile.clone(), profile));
+}
+
+/// Writes the profile recorded so far, if any.
+pub(crate) fn save_profile() {
+    let recording = RECORDING.lock().unwrap();
+    let Some((path, profile)) = recording.as_ref() else {
+        return;
+    };
+
+    let result = serde_json::to_string(profile)
+        .map_err(|e| e.to_string())
+        .and_then(|json| std::fs::write(path, json).map_err(|e| e.to_string()));
+    match result {
+        Ok(()) => debug!("Warm start profile saved to {}", path.to_string_lossy()),
+        Err(e) => error!(
+            "Couldn't save warm start profile to {} - {}",
+            path.to_string_lossy(),
+            e
+        ),
+    }
+}
@@ -0,0 +1,178 @@
+/*
+ * Copyright (c) 2022 Contributors to the bevy-rrise project
+ */
+
+use crate::diagnostics::count_set_rtpc;
+use crate::emitter_listener::{RrListener, RrRegistered};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use rrise::game_syncs::{set_state, SetRtpcValue};
+use rrise::{AkID, AkRtpcValue};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Shape of an [RrListenerEffectZone], centered on its entity, in its local space.
+pub enum RrZoneShape {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+}
+
+impl RrZoneShape {
+    /// Approximate signed distance of `point` to the shape; negative inside.
+    fn distance(&self, point: Vec3) -> f32 {
+        match *self {
+            RrZoneShape::Sphere { radius } => point.length() - radius,
+            RrZoneShape::Box { half_extents } => (point.abs() - half_extents).max_element(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+/// Game sync applied by an [RrListenerEffectZone].
+pub enum RrZoneEffect {
+    /// Sets the state group `group` to `inside` when the listener enters the zone, and back to
+    /// `outside` when it exits it.
+    ///
+    /// `group`, `inside` and `outside` should all be names or all be IDs.
+    State {
+        group: AkID<'static>,
+        inside: AkID<'static>,
+        outside: AkID<'static>,
+    },
+
+    /// Sets the global RTPC `rtpc` to `inside` when the listener enters the zone, and back to
+    /// `outside` when it exits it.
+    Rtpc {
+        rtpc: AkID<'static>,
+        inside: AkRtpcValue,
+        outside: AkRtpcValue,
+    },
+}
+
+impl RrZoneEffect {
+    fn apply(&self, inside: bool) {
+        match *self {
+            RrZoneEffect::State {
+                group,
+                inside: state_in,
+                outside: state_out,
+            } => {
+                let state = if inside { state_in } else { state_out };
+                if let Err(akr) = set_state(group, state) {
+                    error!("Couldn't set state '{}' to '{}' - {}", group, state, akr);
+                }
+            }
+            RrZoneEffect::Rtpc {
+                rtpc,
+                inside: value_in,
+                outside: value_out,
+            } => {
+                count_set_rtpc();
+                let value = if inside { value_in } else { value_out };
+                if let Err(akr) = SetRtpcValue::new(rtpc, value).set() {
+                    error!("Couldn't set RTPC '{}' - {}", rtpc, akr);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Component)]
+/// Zone applying an [RrZoneEffect] while the default listener is in it, like an underwater
+/// state or an indoor low-pass RTPC.
+///
+/// The listener enters the zone as soon as it is inside its shape, but only exits it once
+/// farther than [Self::hysteresis] from it, so that hovering at the boundary doesn't flicker.
+/// If the zone gets despawned while the listener is in it, the outside value is applied.
+///
+/// ### Example
+/// ```rust,ignore
+/// commands.spawn((
+///     SpatialBundle::from_transform(Transform::from_xyz(0., -50., 0.)),
+///     RrListenerEffectZone::new(
+///         RrZoneShape::Box { half_extents: Vec3::new(100., 50., 100.) },
+///         RrZoneEffect::State {
+///             group: "Environment".into(),
+///             inside: "Underwater".into(),
+///             outside: "Air".into(),
+///         },
+///     )
+///     .with_hysteresis(0.5),
+/// ));
+/// ```
+pub struct RrListenerEffectZone {
+    pub shape: RrZoneShape,
+    pub effect: RrZoneEffect,
+
+    /// Distance from the shape, in its local space, the listener must reach to exit the zone.
+    ///
+    /// Defaults to `0.`.
+    pub hysteresis: f32,
+}
+
+impl RrListenerEffectZone {
+    pub fn new(shape: RrZoneShape, effect: RrZoneEffect) -> Self {
+        Self {
+            shape,
+            effect,
+            hysteresis: 0.,
+        }
+    }
+
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+}
+
+/// Zones the default listener is in, with the effect to revert when leaving them.
+#[derive(Default)]
+pub(crate) struct OccupiedZones(HashMap<Entity, RrZoneEffect>);
+
+#[tracing::instrument(level = "debug", skip_all)]
+pub(crate) fn update_listener_effect_zones(
+    zones: Query<(Entity, &RrListenerEffectZone, &GlobalTransform)>,
+    listeners: Query<(&RrListener, &GlobalTransform), With<RrRegistered>>,
+    mut occupied: Local<OccupiedZones>,
+) {
+    let listener_pos = listeners
+        .iter()
+        .find(|(rr_l, _)| rr_l.is_default())
+        .map(|(_, tfm)| tfm.translation());
+
+    for (e, zone, tfm) in zones.iter() {
+        let was_inside = occupied.0.contains_key(&e);
+        let inside = match listener_pos {
+            Some(pos) => {
+                let local_pos = tfm.affine().inverse().transform_point3(pos);
+                let distance = zone.shape.distance(local_pos);
+                distance <= if was_inside { zone.hysteresis } else { 0. }
+            }
+            None => false,
+        };
+
+        if inside == was_inside {
+            continue;
+        }
+
+        debug!(
+            "Listener {} zone {}",
+            if inside { "entered" } else { "exited" },
+            e.index()
+        );
+        if inside {
+            occupied.0.insert(e, zone.effect);
+        } else {
+            occupied.0.remove(&e);
+        }
+        zone.effect.apply(inside);
+    }
+
+    occupied.0.retain(|&e, effect| {
+        if zones.contains(e) {
+            return true;
+        }
+        debug!("Listener zone {} removed while occupied", e.index());
+        effect.apply(false);
+        false
+    });
+}